@@ -0,0 +1,98 @@
+use crate::game::menu::DifficultyLevel;
+
+// How the price of a tower grows with each copy already on the field
+#[derive(Debug, Clone, Copy)]
+pub enum PriceCurve {
+    Linear { step: f32 },        // +step * base for each copy owned
+    Exponential { factor: f32 }, // base * factor ^ copies owned
+}
+
+impl PriceCurve {
+    pub fn price(&self, base: u32, owned: u32) -> u32 {
+        let multiplier = match self {
+            PriceCurve::Linear { step } => 1.0 + step * owned as f32,
+            PriceCurve::Exponential { factor } => factor.powi(owned as i32),
+        };
+        (base as f32 * multiplier).round() as u32
+    }
+}
+
+// Gold rules that depend on the chosen difficulty
+#[derive(Debug, Clone, Copy)]
+pub struct EconomyRules {
+    interest_enabled: bool,
+    interest_rate: f32,    // fraction of banked gold paid when a wave starts
+    interest_cap: u32,     // maximum interest paid per wave
+    early_call_enabled: bool,
+    early_bonus_per_second: f32, // gold per second of cooldown skipped
+    duplicate_pricing_enabled: bool,
+    duplicate_pricing: PriceCurve, // used while duplicate pricing is on
+}
+
+impl EconomyRules {
+    pub fn for_difficulty(difficulty: DifficultyLevel) -> Self {
+        match difficulty {
+            DifficultyLevel::Easy => Self {
+                interest_enabled: true,
+                interest_rate: 0.10,
+                interest_cap: 100,
+                early_call_enabled: true,
+                early_bonus_per_second: 3.0,
+                duplicate_pricing_enabled: false,
+                duplicate_pricing: PriceCurve::Linear { step: 0.15 },
+            },
+            DifficultyLevel::Normal => Self {
+                interest_enabled: true,
+                interest_rate: 0.05,
+                interest_cap: 50,
+                early_call_enabled: true,
+                early_bonus_per_second: 2.0,
+                duplicate_pricing_enabled: false,
+                duplicate_pricing: PriceCurve::Linear { step: 0.25 },
+            },
+            DifficultyLevel::Hard => Self {
+                interest_enabled: false,
+                interest_rate: 0.0,
+                interest_cap: 0,
+                early_call_enabled: true,
+                early_bonus_per_second: 1.0,
+                duplicate_pricing_enabled: true,
+                duplicate_pricing: PriceCurve::Exponential { factor: 1.25 },
+            },
+        }
+    }
+
+    pub fn interest_on(&self, gold: u32) -> u32 {
+        if !self.interest_enabled {
+            return 0;
+        }
+        ((gold as f32 * self.interest_rate) as u32).min(self.interest_cap)
+    }
+
+    pub fn early_call_bonus(&self, seconds_skipped: f32) -> u32 {
+        if !self.early_call_enabled || seconds_skipped <= 0.0 {
+            return 0;
+        }
+        (seconds_skipped * self.early_bonus_per_second) as u32
+    }
+
+    // Price of the next tower when `owned` of the same type are already placed
+    pub fn tower_price(&self, base: u32, owned: u32) -> u32 {
+        if !self.duplicate_pricing_enabled {
+            return base;
+        }
+        self.duplicate_pricing.price(base, owned)
+    }
+
+    pub fn set_duplicate_pricing(&mut self, enabled: bool) {
+        self.duplicate_pricing_enabled = enabled;
+    }
+
+    pub fn is_duplicate_pricing_enabled(&self) -> bool {
+        self.duplicate_pricing_enabled
+    }
+
+    pub fn is_early_call_enabled(&self) -> bool {
+        self.early_call_enabled
+    }
+}
@@ -0,0 +1,38 @@
+// Modifiers rolled onto regular enemies in later waves, on top of their type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Affix {
+    Armored,      // flat damage reduction on every hit
+    Hasted,       // moves faster
+    Regenerating, // slowly heals itself
+    Vampiric,     // drains life from enemies dying nearby
+    Explosive,    // stuns nearby towers when it dies
+}
+
+impl Affix {
+    pub const ALL: [Affix; 5] = [
+        Affix::Armored,
+        Affix::Hasted,
+        Affix::Regenerating,
+        Affix::Vampiric,
+        Affix::Explosive,
+    ];
+
+    pub const ARMOR: u32 = 3;
+    pub const SPEED_MULTIPLIER: f32 = 1.4;
+    pub const REGEN_FRACTION: f32 = 0.03; // of max health per second
+    pub const VAMPIRIC_RADIUS: f32 = 80.0;
+    pub const VAMPIRIC_DRAIN: f32 = 0.25; // of the dying enemy's max health
+    pub const EXPLOSION_RADIUS: f32 = 60.0;
+    pub const STUN_DURATION: f32 = 2.0;
+
+    // Colour of the ring drawn around enemies carrying this affix
+    pub fn color(&self) -> [f32; 4] {
+        match self {
+            Affix::Armored => [0.75, 0.75, 0.75, 1.0],
+            Affix::Hasted => [1.0, 1.0, 0.2, 1.0],
+            Affix::Regenerating => [0.2, 1.0, 0.4, 1.0],
+            Affix::Vampiric => [0.7, 0.0, 0.2, 1.0],
+            Affix::Explosive => [1.0, 0.45, 0.0, 1.0],
+        }
+    }
+}
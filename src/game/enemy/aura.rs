@@ -0,0 +1,6 @@
+// Effects support enemies give to other enemies around them
+#[derive(Debug, Clone, Copy)]
+pub enum SupportAura {
+    Heal { per_second: f32 },
+    Armor { amount: u32, duration: f32 }, // flat damage reduction, refreshed while in range
+}
@@ -8,12 +8,45 @@ impl Enemy1 {
             enemy_type: EnemyType::Normal,
             x,
             y,
+            velocity: (0.0, 0.0),
+            heading: (0.0, 0.0),
+            lane_offset: 0.0,
+            lateral: 0.0,
             speed: 50.0,
             health: 100,
             max_health: 100,
             damage: 10,
             reward: 20,
-            path_index: 0,
+            radius: 8.0,
+            shield: 0,
+            max_shield: 0,
+            shield_regen: 0.0,
+            shield_delay: 0.0,
+            shield_timer: 0.0,
+            shield_carry: 0.0,
+            phases: Vec::new(),
+            next_phase: 0,
+            pending_minions: 0,
+            flying: false,
+            stealth: false,
+            detected: false,
+            split_into: None,
+            aura: None,
+            aura_radius: 0.0,
+            armor: 0,
+            armor_time: 0.0,
+            heal_carry: 0.0,
+            affixes: Vec::new(),
+            siege: None,
+            siege_cooldown: 0.0,
+            sieging: false,
+            path_skip: None,
+            skip_timer: 0.0,
+            burrowed: false,
+            distance: 0.0,
+            route: 0,
+            knockback_resistance: 0.0,
+            formation: None,
             spawn_delay,
             active: false,
         }
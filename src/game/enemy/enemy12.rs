@@ -0,0 +1,58 @@
+use super::{Enemy, EnemyType, SiegeAttack, SiegeEffect};
+
+pub struct Enemy12;
+
+impl Enemy12 {
+    pub fn create(x: f32, y: f32, spawn_delay: f32) -> Enemy {
+        Enemy {
+            enemy_type: EnemyType::Disruptor,
+            x,
+            y,
+            velocity: (0.0, 0.0),
+            heading: (0.0, 0.0),
+            lane_offset: 0.0,
+            lateral: 0.0,
+            speed: 50.0,
+            health: 90,
+            max_health: 90,
+            damage: 8,
+            reward: 40,
+            radius: 8.0,
+            shield: 0,
+            max_shield: 0,
+            shield_regen: 0.0,
+            shield_delay: 0.0,
+            shield_timer: 0.0,
+            shield_carry: 0.0,
+            phases: Vec::new(),
+            next_phase: 0,
+            pending_minions: 0,
+            flying: false,
+            stealth: false,
+            detected: false,
+            split_into: None,
+            aura: None,
+            aura_radius: 0.0,
+            armor: 0,
+            armor_time: 0.0,
+            heal_carry: 0.0,
+            affixes: Vec::new(),
+            siege: Some(SiegeAttack {
+                effect: SiegeEffect::Emp(3.0), // Knocks towers out for a few seconds
+                range: 60.0,
+                interval: 6.0,
+            }),
+            siege_cooldown: 0.0,
+            sieging: false,
+            path_skip: None,
+            skip_timer: 0.0,
+            burrowed: false,
+            distance: 0.0,
+            route: 0,
+            knockback_resistance: 0.0,
+            formation: None,
+            spawn_delay,
+            active: false,
+        }
+    }
+}
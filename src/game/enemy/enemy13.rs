@@ -0,0 +1,54 @@
+use super::{Enemy, EnemyType, PathSkip};
+
+pub struct Enemy13;
+
+impl Enemy13 {
+    pub fn create(x: f32, y: f32, spawn_delay: f32) -> Enemy {
+        Enemy {
+            enemy_type: EnemyType::Blinker,
+            x,
+            y,
+            velocity: (0.0, 0.0),
+            heading: (0.0, 0.0),
+            lane_offset: 0.0,
+            lateral: 0.0,
+            speed: 45.0,
+            health: 70,
+            max_health: 70,
+            damage: 10,
+            reward: 35,
+            radius: 7.0,
+            shield: 0,
+            max_shield: 0,
+            shield_regen: 0.0,
+            shield_delay: 0.0,
+            shield_timer: 0.0,
+            shield_carry: 0.0,
+            phases: Vec::new(),
+            next_phase: 0,
+            pending_minions: 0,
+            flying: false,
+            stealth: false,
+            detected: false,
+            split_into: None,
+            aura: None,
+            aura_radius: 0.0,
+            armor: 0,
+            armor_time: 0.0,
+            heal_carry: 0.0,
+            affixes: Vec::new(),
+            siege: None,
+            siege_cooldown: 0.0,
+            sieging: false,
+            path_skip: Some(PathSkip::Blink { distance: 80.0, cooldown: 4.0 }), // Vanishes and reappears further on
+            skip_timer: 4.0,
+            burrowed: false,
+            distance: 0.0,
+            route: 0,
+            knockback_resistance: 0.0,
+            formation: None,
+            spawn_delay,
+            active: false,
+        }
+    }
+}
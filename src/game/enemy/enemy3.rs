@@ -0,0 +1,58 @@
+use super::{BossPhase, Enemy, EnemyType, PhaseEffect};
+
+pub struct Enemy3;
+
+impl Enemy3 {
+    pub fn create(x: f32, y: f32, spawn_delay: f32) -> Enemy {
+        Enemy {
+            enemy_type: EnemyType::Boss,
+            x,
+            y,
+            velocity: (0.0, 0.0),
+            heading: (0.0, 0.0),
+            lane_offset: 0.0,
+            lateral: 0.0,
+            speed: 35.0,  // Slow and heavy
+            health: 600,
+            max_health: 600,
+            damage: 25,
+            reward: 100,
+            radius: 16.0,
+            shield: 0,
+            max_shield: 0,
+            shield_regen: 0.0,
+            shield_delay: 0.0,
+            shield_timer: 0.0,
+            shield_carry: 0.0,
+            phases: vec![
+                BossPhase { health_fraction: 0.75, effect: PhaseEffect::SpawnMinions(3) },
+                BossPhase { health_fraction: 0.5, effect: PhaseEffect::Shield(150) },
+                BossPhase { health_fraction: 0.25, effect: PhaseEffect::SpeedUp(1.8) },
+            ],
+            next_phase: 0,
+            pending_minions: 0,
+            flying: false,
+            stealth: false,
+            detected: false,
+            split_into: None,
+            aura: None,
+            aura_radius: 0.0,
+            armor: 0,
+            armor_time: 0.0,
+            heal_carry: 0.0,
+            affixes: Vec::new(),
+            siege: None,
+            siege_cooldown: 0.0,
+            sieging: false,
+            path_skip: None,
+            skip_timer: 0.0,
+            burrowed: false,
+            distance: 0.0,
+            route: 0,
+            knockback_resistance: 0.8, // Barely budges when shoved
+            formation: None,
+            spawn_delay,
+            active: false,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use super::EnemyType;
+
+// Groups that spawn together and march in a fixed shape behind a leader
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormationKind {
+    Escort,  // a boss with shielded guards around it
+    Phalanx, // a tight block of grunts
+}
+
+// Where one member marches relative to the leader
+#[derive(Debug, Clone, Copy)]
+pub struct FormationSlot {
+    pub enemy_type: EnemyType,
+    pub along: f32, // pixels ahead of the leader along the route, negative is behind
+    pub lane: f32,  // lane as a fraction of the path half-width, -1 to 1
+}
+
+impl FormationKind {
+    pub fn leader(&self) -> EnemyType {
+        match self {
+            FormationKind::Escort => EnemyType::Boss,
+            FormationKind::Phalanx => EnemyType::Normal,
+        }
+    }
+
+    // Members other than the leader
+    pub fn slots(&self) -> Vec<FormationSlot> {
+        match self {
+            FormationKind::Escort => [(28.0, -1.0), (28.0, 1.0), (-28.0, -1.0), (-28.0, 1.0)]
+                .iter()
+                .map(|&(along, lane)| FormationSlot { enemy_type: EnemyType::Shielded, along, lane })
+                .collect(),
+            // Three rows of three, the leader in the middle of the front row
+            FormationKind::Phalanx => (0..3)
+                .flat_map(|row| (0..3).map(move |column| (row, column)))
+                .filter(|&slot| slot != (0, 1))
+                .map(|(row, column)| FormationSlot {
+                    enemy_type: EnemyType::Normal,
+                    along: row as f32 * -16.0,
+                    lane: column as f32 - 1.0,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FormationKind::Escort => "Escort",
+            FormationKind::Phalanx => "Phalanx",
+        }
+    }
+}
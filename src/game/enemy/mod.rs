@@ -1,52 +1,191 @@
+mod affix;
+mod aura;
 mod enemy1;
+mod enemy10;
+mod enemy11;
+mod enemy12;
+mod enemy13;
+mod enemy14;
+mod enemy15;
 mod enemy2;
+mod enemy3;
+mod enemy4;
+mod enemy5;
+mod enemy6;
+mod enemy7;
+mod enemy8;
+mod enemy9;
+mod formation;
+mod movement;
+mod phase;
+mod siege;
 
 use ggez::GameResult;
 use ggez::graphics::{DrawMode, DrawParam, Mesh, Rect, Canvas};
 use glam::Vec2;
-use crate::game::map::Map;
+use crate::game::map::{Map, PATH_WIDTH};
+use crate::game::tower::DamageType;
 
+pub use affix::Affix;
+pub use aura::SupportAura;
 pub use enemy1::Enemy1;
+pub use enemy10::Enemy10;
+pub use enemy11::Enemy11;
+pub use enemy12::Enemy12;
+pub use enemy13::Enemy13;
+pub use enemy14::Enemy14;
+pub use enemy15::Enemy15;
 pub use enemy2::Enemy2;
+pub use enemy3::Enemy3;
+pub use enemy4::Enemy4;
+pub use enemy5::Enemy5;
+pub use enemy6::Enemy6;
+pub use enemy7::Enemy7;
+pub use enemy8::Enemy8;
+pub use enemy9::Enemy9;
+pub use formation::FormationKind;
+pub use movement::PathSkip;
+pub use phase::{BossPhase, PhaseEffect};
+pub use siege::{SiegeAttack, SiegeEffect};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum EnemyType {
     Normal,
     Fast,
     Boss,
+    Flying,
+    Splitter,
+    Splitling,
+    Healer,
+    Warden,
+    Shielded,
+    Stealth,
+    Sapper,
+    Disruptor,
+    Blinker,
+    Burrower,
+    Swarmer,
 }
 
 pub struct Enemy {
     enemy_type: EnemyType,
     x: f32,
     y: f32,
+    velocity: (f32, f32), // pixels per second over the last update
+    heading: (f32, f32),  // unit direction along the current path segment
+    lane_offset: f32,     // preferred sideways offset from the path centre
+    lateral: f32,         // current sideways offset, pushed around by crowding
     speed: f32,
     health: u32,
     max_health: u32,
     damage: u32,
     reward: u32,
-    path_index: usize,
+    radius: f32,            // collision radius for hits, splash and drawing
+    shield: u32,            // soaks up damage before health
+    max_shield: u32,        // regeneration stops here; boss phase shields go above it
+    shield_regen: f32,      // shield points per second
+    shield_delay: f32,      // seconds without being hit before regeneration starts
+    shield_timer: f32,      // seconds since the last hit
+    shield_carry: f32,      // fractional regeneration not yet applied
+    phases: Vec<BossPhase>, // ordered from highest threshold down
+    next_phase: usize,
+    pending_minions: u32,   // minions a phase change wants spawned
+    flying: bool,
+    stealth: bool,          // towers can only target it while detected
+    detected: bool,         // inside a detection source this tick
+    split_into: Option<(EnemyType, u32)>, // children spawned on death
+    aura: Option<SupportAura>, // effect given to other enemies nearby
+    aura_radius: f32,
+    armor: u32,                // flat damage reduction while armor_time lasts
+    armor_time: f32,
+    heal_carry: f32,           // fractional healing not yet applied
+    affixes: Vec<Affix>,       // elite modifiers rolled at spawn
+    siege: Option<SiegeAttack>, // attack used on towers next to the path
+    siege_cooldown: f32,
+    sieging: bool,             // stopped to attack a tower
+    path_skip: Option<PathSkip>,
+    skip_timer: f32,           // seconds until the next blink or burrow change
+    burrowed: bool,            // underground, can't be targeted or hit
+    distance: f32, // progress along the route in pixels
+    route: usize,  // which of the map's ground routes it is following
+    knockback_resistance: f32, // share of knockback and pulls ignored, 0 to 1
+    formation: Option<(f32, f32)>, // slot progress and leader's pace while marching in formation
     spawn_delay: f32,
     active: bool,
 }
 
+impl EnemyType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnemyType::Normal => "Grunt",
+            EnemyType::Fast => "Runner",
+            EnemyType::Boss => "Warlord",
+            EnemyType::Flying => "Wyvern",
+            EnemyType::Splitter => "Splitter",
+            EnemyType::Splitling => "Splitling",
+            EnemyType::Healer => "Mender",
+            EnemyType::Warden => "Warden",
+            EnemyType::Shielded => "Aegis",
+            EnemyType::Stealth => "Shade",
+            EnemyType::Sapper => "Sapper",
+            EnemyType::Disruptor => "Disruptor",
+            EnemyType::Blinker => "Phaser",
+            EnemyType::Burrower => "Tunneler",
+            EnemyType::Swarmer => "Swarmer",
+        }
+    }
+}
+
 impl Enemy {
     pub fn new(enemy_type: EnemyType, x: f32, y: f32, spawn_delay: f32) -> Self {
         match enemy_type {
             EnemyType::Normal => Enemy1::create(x, y, spawn_delay),
             EnemyType::Fast => Enemy2::create(x, y, spawn_delay),
-            EnemyType::Boss => {
-                let mut enemy = Enemy1::create(x, y, spawn_delay);
-                enemy.health *= 3;
-                enemy.max_health *= 3;
-                enemy.damage *= 2;
-                enemy.reward *= 3;
-                enemy.speed *= 0.7;
-                enemy
-            }
+            EnemyType::Boss => Enemy3::create(x, y, spawn_delay),
+            EnemyType::Flying => Enemy4::create(x, y, spawn_delay),
+            EnemyType::Splitter => Enemy5::create(x, y, spawn_delay),
+            EnemyType::Splitling => Enemy6::create(x, y, spawn_delay),
+            EnemyType::Healer => Enemy7::create(x, y, spawn_delay),
+            EnemyType::Warden => Enemy8::create(x, y, spawn_delay),
+            EnemyType::Shielded => Enemy9::create(x, y, spawn_delay),
+            EnemyType::Stealth => Enemy10::create(x, y, spawn_delay),
+            EnemyType::Sapper => Enemy11::create(x, y, spawn_delay),
+            EnemyType::Disruptor => Enemy12::create(x, y, spawn_delay),
+            EnemyType::Blinker => Enemy13::create(x, y, spawn_delay),
+            EnemyType::Burrower => Enemy14::create(x, y, spawn_delay),
+            EnemyType::Swarmer => Enemy15::create(x, y, spawn_delay),
         }
     }
 
+    // A new enemy that starts where this one is, already on the path
+    pub fn spawn_at(&self, enemy_type: EnemyType) -> Self {
+        let mut enemy = Enemy::new(enemy_type, self.x, self.y, 0.0);
+        enemy.distance = self.distance;
+        enemy.route = self.route;
+        enemy.heading = self.heading;
+        enemy.lane_offset = self.lane_offset.clamp(-enemy.max_lateral(), enemy.max_lateral());
+        enemy.lateral = self.lateral.clamp(-enemy.max_lateral(), enemy.max_lateral());
+        enemy.active = true;
+        enemy
+    }
+
+    // Children a splitter leaves behind where it died, fanned out side by
+    // side so they don't stack into one dot
+    pub fn spawn_children(&self) -> Vec<Enemy> {
+        let Some((child_type, count)) = self.split_into else {
+            return Vec::new();
+        };
+        
+        (0..count)
+            .map(|i| {
+                let mut child = self.spawn_at(child_type);
+                let spread = (i as f32 - (count - 1) as f32 / 2.0) * 6.0;
+                child.lateral = (self.lateral + spread).clamp(-child.max_lateral(), child.max_lateral());
+                child
+            })
+            .collect()
+    }
+
     // Returns true if the enemy reached the end
     pub fn update(&mut self, dt: f32, map: &Map) -> bool {
         if self.spawn_delay > 0.0 {
@@ -58,25 +197,45 @@ impl Enemy {
             self.active = true;
         }
         
-        let path = map.get_path();
-        if self.path_index >= path.len() - 1 {
-            return true; // Reached end of path
+        self.armor_time = (self.armor_time - dt).max(0.0);
+        if self.armor_time <= 0.0 {
+            self.armor = 0;
         }
         
-        let target = path[self.path_index + 1];
-        let dx = target.0 - self.x;
-        let dy = target.1 - self.y;
-        let dist = (dx * dx + dy * dy).sqrt();
+        if self.has_affix(Affix::Regenerating) {
+            self.heal(self.max_health as f32 * Affix::REGEN_FRACTION * dt);
+        }
         
-        if dist <= self.speed * dt {
-            self.x = target.0;
-            self.y = target.1;
-            self.path_index += 1;
-        } else {
-            self.x += dx / dist * self.speed * dt;
-            self.y += dy / dist * self.speed * dt;
+        // Shields come back after a while without being hit
+        self.shield_timer += dt;
+        if self.shield_timer >= self.shield_delay && self.shield < self.max_shield {
+            self.shield_carry += self.shield_regen * dt;
+            let whole = self.shield_carry.floor();
+            self.shield_carry -= whole;
+            self.shield = (self.shield + whole as u32).min(self.max_shield);
         }
         
+        // Siege enemies hold their ground while attacking a tower
+        if self.sieging {
+            self.velocity = (0.0, 0.0);
+            return false;
+        }
+        
+        let speed = match self.formation {
+            Some((slot, pace)) => Self::formation_speed(slot - self.distance, pace),
+            None => self.speed,
+        };
+        self.distance += speed * dt + self.update_path_skip(dt);
+        let Some((position, heading)) = map.route_point(self.route, self.flying, self.distance) else {
+            return true; // Reached end of path
+        };
+        
+        self.x = position.0;
+        self.y = position.1;
+        self.heading = heading;
+        // Walking speed only, so a blink doesn't throw off shells aimed ahead
+        self.velocity = (heading.0 * speed, heading.1 * speed);
+        
         // If health is zero, the enemy is dead but we'll let the game system
         // handle removal after collecting the reward
         if self.health == 0 {
@@ -91,31 +250,134 @@ impl Enemy {
             return Ok(());
         }
         
+        let (x, y) = self.get_position();
+        
+        // Only a mound of dirt shows where a burrower is tunnelling
+        if self.burrowed {
+            let mound = Mesh::new_circle(
+                ctx,
+                DrawMode::fill(),
+                Vec2::new(x, y),
+                self.radius * 0.8,
+                0.1,
+                [0.45, 0.3, 0.15, 0.6].into(),
+            )?;
+            canvas.draw(&mound, DrawParam::default());
+            return Ok(());
+        }
+        
+        // Show the reach of a support aura
+        if let Some(aura) = self.aura {
+            let aura_ring = Mesh::new_circle(
+                ctx,
+                DrawMode::stroke(1.0),
+                Vec2::new(x, y),
+                self.aura_radius,
+                0.5,
+                match aura {
+                    SupportAura::Heal { .. } => [0.3, 1.0, 0.3, 0.3].into(),
+                    SupportAura::Armor { .. } => [0.7, 0.7, 0.7, 0.3].into(),
+                },
+            )?;
+            canvas.draw(&aura_ring, DrawParam::default());
+        }
+        
+        // Armored enemies get a grey outline
+        if self.armor > 0 {
+            let armor_ring = Mesh::new_circle(
+                ctx,
+                DrawMode::stroke(2.0),
+                Vec2::new(x, y),
+                self.radius + 1.5,
+                0.1,
+                [0.75, 0.75, 0.75, 1.0].into(),
+            )?;
+            canvas.draw(&armor_ring, DrawParam::default());
+        }
+        
+        // One coloured ring per elite affix
+        for (i, affix) in self.affixes.iter().enumerate() {
+            let affix_ring = Mesh::new_circle(
+                ctx,
+                DrawMode::stroke(1.5),
+                Vec2::new(x, y),
+                self.radius + 4.0 + i as f32 * 2.5,
+                0.1,
+                affix.color().into(),
+            )?;
+            canvas.draw(&affix_ring, DrawParam::default());
+        }
+        
+        // Flyers cast a shadow on the ground below them
+        if self.flying {
+            let shadow = Mesh::new_circle(
+                ctx,
+                DrawMode::fill(),
+                Vec2::new(x + 6.0, y + 12.0),
+                self.radius * 0.8,
+                0.1,
+                [0.0, 0.0, 0.0, 0.35].into(),
+            )?;
+            canvas.draw(&shadow, DrawParam::default());
+        }
+        
         // Draw enemy body
+        let mut color = match self.enemy_type {
+            EnemyType::Normal => [0.8, 0.2, 0.2, 1.0],
+            EnemyType::Fast => [0.2, 0.8, 0.2, 1.0],
+            EnemyType::Boss => [0.8, 0.4, 0.0, 1.0],
+            EnemyType::Flying => [0.6, 0.8, 1.0, 1.0],
+            EnemyType::Splitter => [0.6, 0.2, 0.6, 1.0],
+            EnemyType::Splitling => [0.8, 0.4, 0.8, 1.0],
+            EnemyType::Healer => [0.2, 0.9, 0.6, 1.0],
+            EnemyType::Warden => [0.5, 0.5, 0.6, 1.0],
+            EnemyType::Shielded => [0.3, 0.4, 0.9, 1.0],
+            EnemyType::Stealth => [0.3, 0.3, 0.3, 1.0],
+            EnemyType::Sapper => [0.55, 0.35, 0.2, 1.0],
+            EnemyType::Disruptor => [0.2, 0.6, 1.0, 1.0],
+            EnemyType::Blinker => [0.7, 0.3, 1.0, 1.0],
+            EnemyType::Burrower => [0.6, 0.45, 0.25, 1.0],
+            EnemyType::Swarmer => [0.9, 0.9, 0.3, 1.0],
+        };
+        // Undetected stealth enemies are only faintly visible
+        if self.is_hidden() {
+            color[3] = 0.3;
+        }
         let circle = Mesh::new_circle(
             ctx,
             DrawMode::fill(),
-            Vec2::new(self.x, self.y),
-            8.0,
+            Vec2::new(x, y),
+            self.radius,
             0.1,
-            match self.enemy_type {
-                EnemyType::Normal => [0.8, 0.2, 0.2, 1.0].into(),
-                EnemyType::Fast => [0.2, 0.8, 0.2, 1.0].into(),
-                EnemyType::Boss => [0.8, 0.4, 0.0, 1.0].into(),
-            },
+            color.into(),
         )?;
         
         canvas.draw(&circle, DrawParam::default());
         
-        // Draw health bar
-        let health_pct = self.health as f32 / self.max_health as f32;
+        // Draw a ring while a shield is up
+        if self.shield > 0 {
+            let shield_ring = Mesh::new_circle(
+                ctx,
+                DrawMode::stroke(2.0),
+                Vec2::new(x, y),
+                self.radius + 3.0,
+                0.1,
+                [0.4, 0.8, 1.0, 0.8].into(),
+            )?;
+            canvas.draw(&shield_ring, DrawParam::default());
+        }
+        
+        // Draw health bar, with the shield as a second segment after the health
+        let bar_total = (self.max_health + self.max_shield.max(self.shield)) as f32;
+        let health_pct = self.health as f32 / bar_total;
+        let bar_width = self.radius * 2.5;
         let health_bar = Mesh::new_rectangle(
             ctx,
             DrawMode::fill(),
             Rect::new(
-                self.x - 10.0,
-                self.y - 15.0,
-                20.0 * health_pct,
+                x - bar_width / 2.0,
+                y - self.radius - 7.0,
+                bar_width * health_pct,
                 3.0,
             ),
             [0.0, 1.0, 0.0, 1.0].into(),
@@ -123,16 +385,260 @@ impl Enemy {
         
         canvas.draw(&health_bar, DrawParam::default());
         
+        if self.shield > 0 {
+            let shield_pct = self.shield as f32 / bar_total;
+            let shield_bar = Mesh::new_rectangle(
+                ctx,
+                DrawMode::fill(),
+                Rect::new(
+                    x - bar_width / 2.0 + bar_width * health_pct,
+                    y - self.radius - 7.0,
+                    bar_width * shield_pct,
+                    3.0,
+                ),
+                [0.4, 0.8, 1.0, 1.0].into(),
+            )?;
+            canvas.draw(&shield_bar, DrawParam::default());
+        }
+        
         Ok(())
     }
     
-    pub fn take_damage(&mut self, damage: u32) -> bool {
+    // Returns the health actually lost after armor and shields, the shield
+    // points knocked off, and whether the enemy died
+    pub fn take_damage(&mut self, damage: u32, damage_type: DamageType) -> (u32, u32, bool) {
+        self.shield_timer = 0.0;
+        
+        // Armor takes a flat amount off every hit, shields then soak up the rest
+        let armor = if self.has_affix(Affix::Armored) { self.armor.max(Affix::ARMOR) } else { self.armor };
+        let damage = if armor > 0 { damage.saturating_sub(armor).max(1) } else { damage };
+        let mut absorbed = 0;
+        let damage = if damage_type.bypasses_shields() || self.shield == 0 {
+            damage
+        } else {
+            // Work out the hit in shield points, then turn what's left back into health damage
+            let multiplier = damage_type.shield_multiplier();
+            let against_shield = (damage as f32 * multiplier).round() as u32;
+            absorbed = against_shield.min(self.shield);
+            self.shield -= absorbed;
+            ((against_shield - absorbed) as f32 / multiplier).round() as u32
+        };
+        
         if damage >= self.health {
+            let lost = self.health;
             self.health = 0;
-            return true; // Enemy died
+            return (lost, absorbed, true); // Enemy died
         }
         self.health -= damage;
-        false
+        self.check_phases();
+        (damage, absorbed, false)
+    }
+    
+    // Apply every boss phase whose health threshold has been crossed
+    fn check_phases(&mut self) {
+        while let Some(phase) = self.phases.get(self.next_phase).copied() {
+            if self.health as f32 > self.max_health as f32 * phase.health_fraction {
+                break;
+            }
+            
+            match phase.effect {
+                PhaseEffect::SpeedUp(multiplier) => self.speed *= multiplier,
+                PhaseEffect::SpawnMinions(count) => self.pending_minions += count,
+                PhaseEffect::Shield(amount) => self.shield += amount,
+            }
+            self.next_phase += 1;
+        }
+    }
+    
+    // Restore health up to max_health; the dead stay dead
+    pub fn heal(&mut self, amount: f32) {
+        if self.health == 0 {
+            return;
+        }
+        
+        self.heal_carry += amount;
+        let whole = self.heal_carry.floor();
+        self.heal_carry -= whole;
+        self.health = (self.health + whole as u32).min(self.max_health);
+    }
+    
+    pub fn grant_armor(&mut self, amount: u32, duration: f32) {
+        self.armor = self.armor.max(amount);
+        self.armor_time = self.armor_time.max(duration);
+    }
+    
+    // Make this an elite: apply the affix's stat changes and raise the reward
+    pub fn add_affix(&mut self, affix: Affix) {
+        if self.has_affix(affix) {
+            return;
+        }
+        
+        if affix == Affix::Hasted {
+            self.speed *= Affix::SPEED_MULTIPLIER;
+        }
+        self.reward += self.reward / 2;
+        self.affixes.push(affix);
+    }
+    
+    pub fn has_affix(&self, affix: Affix) -> bool {
+        self.affixes.contains(&affix)
+    }
+    
+    // Extra distance covered by a blink this tick; also flips burrowers
+    // between walking on the surface and tunnelling underneath it
+    fn update_path_skip(&mut self, dt: f32) -> f32 {
+        let Some(skip) = self.path_skip else {
+            return 0.0;
+        };
+        
+        self.skip_timer -= dt;
+        if self.skip_timer > 0.0 {
+            return 0.0;
+        }
+        
+        match skip {
+            PathSkip::Blink { distance, cooldown } => {
+                self.skip_timer = cooldown;
+                distance
+            }
+            PathSkip::Burrow { surface_time, burrow_time } => {
+                self.burrowed = !self.burrowed;
+                self.skip_timer = if self.burrowed { burrow_time } else { surface_time };
+                0.0
+            }
+        }
+    }
+    
+    // Burrowed enemies can't be targeted or hit by anything
+    pub fn is_untargetable(&self) -> bool {
+        self.burrowed
+    }
+    
+    pub fn is_burrowed(&self) -> bool {
+        self.burrowed
+    }
+    
+    // Shove the enemy back along the route, across waypoints if need be
+    pub fn knock_back(&mut self, distance: f32) {
+        let distance = distance * (1.0 - self.knockback_resistance);
+        self.distance = (self.distance - distance).max(0.0);
+    }
+    
+    // Drag the enemy along the route towards the given progress, forwards
+    // or backwards, by at most `distance`
+    pub fn pull_towards(&mut self, progress: f32, distance: f32) {
+        let distance = distance * (1.0 - self.knockback_resistance);
+        self.distance += (progress - self.distance).clamp(-distance, distance);
+    }
+    
+    // Hold a formation slot: march at the leader's pace and steer back
+    // towards the slot at `progress` along the route. Knockback, pulls and
+    // the like still move the member; it just catches up afterwards.
+    pub fn hold_formation(&mut self, route: usize, progress: f32, pace: f32) {
+        self.route = route;
+        self.formation = Some((progress.max(0.0), pace));
+    }
+    
+    pub fn leave_formation(&mut self) {
+        self.formation = None;
+    }
+    
+    // Members follow their leader's route rather than picking their own
+    pub fn is_in_formation(&self) -> bool {
+        self.formation.is_some()
+    }
+    
+    // Walking speed for a member `gap` pixels behind its slot, never backwards
+    fn formation_speed(gap: f32, pace: f32) -> f32 {
+        const CATCH_UP_RATE: f32 = 1.5;  // fraction of the gap closed per second
+        const MAX_CATCH_UP: f32 = 30.0;  // pixels per second on top of the pace
+        (pace + (gap * CATCH_UP_RATE).clamp(-MAX_CATCH_UP, MAX_CATCH_UP)).max(0.0)
+    }
+    
+    // How fast the enemy is walking right now, after slowdowns and stops
+    pub fn get_pace(&self) -> f32 {
+        Vec2::new(self.velocity.0, self.velocity.1).length()
+    }
+    
+    pub fn get_route(&self) -> usize {
+        self.route
+    }
+    
+    // Switch to another route; only safe while both still share the stretch
+    // the enemy is on
+    pub fn set_route(&mut self, route: usize) {
+        self.route = route;
+    }
+    
+    // Pixels travelled along the route so far
+    pub fn get_progress(&self) -> f32 {
+        self.distance
+    }
+    
+    // Start part of the way along the route instead of at the entrance
+    pub fn set_progress(&mut self, distance: f32) {
+        self.distance = distance.max(0.0);
+    }
+    
+    // Stop while a tower is in reach and attack it whenever the cooldown
+    // allows; returns the effect of an attack that lands this tick
+    pub fn update_siege(&mut self, dt: f32, tower_in_reach: bool) -> Option<SiegeEffect> {
+        let siege = self.siege?;
+        self.siege_cooldown = (self.siege_cooldown - dt).max(0.0);
+        self.sieging = tower_in_reach;
+        if !tower_in_reach || self.siege_cooldown > 0.0 {
+            return None;
+        }
+        
+        self.siege_cooldown = siege.interval;
+        Some(siege.effect)
+    }
+    
+    pub fn get_siege(&self) -> Option<SiegeAttack> {
+        self.siege
+    }
+    
+    // Support aura and its radius, for enemies that help others
+    pub fn get_aura(&self) -> Option<(SupportAura, f32)> {
+        self.aura.map(|aura| (aura, self.aura_radius))
+    }
+    
+    // Minions requested by phase changes since the last call
+    pub fn take_pending_minions(&mut self) -> u32 {
+        std::mem::take(&mut self.pending_minions)
+    }
+    
+    pub fn get_enemy_type(&self) -> EnemyType {
+        self.enemy_type
+    }
+    
+    pub fn is_boss(&self) -> bool {
+        !self.phases.is_empty()
+    }
+    
+    pub fn get_name(&self) -> &'static str {
+        self.enemy_type.name()
+    }
+    
+    pub fn is_stealthy(&self) -> bool {
+        self.stealth
+    }
+    
+    pub fn set_detected(&mut self, detected: bool) {
+        self.detected = detected;
+    }
+    
+    // Stealthy and not currently inside any detection source
+    pub fn is_hidden(&self) -> bool {
+        self.stealth && !self.detected
+    }
+    
+    pub fn get_max_health(&self) -> u32 {
+        self.max_health
+    }
+    
+    pub fn get_shield(&self) -> u32 {
+        self.shield
     }
     
     pub fn get_reward(&self) -> u32 {
@@ -143,8 +649,46 @@ impl Enemy {
         self.damage
     }
     
+    // Where the enemy actually is: its point on the path, moved sideways
+    // into its lane
     pub fn get_position(&self) -> (f32, f32) {
-        (self.x, self.y)
+        (self.x - self.heading.1 * self.lateral, self.y + self.heading.0 * self.lateral)
+    }
+    
+    pub fn get_heading(&self) -> (f32, f32) {
+        self.heading
+    }
+    
+    pub fn get_radius(&self) -> f32 {
+        self.radius
+    }
+    
+    // Furthest the enemy's centre may stray from the middle of the path
+    fn max_lateral(&self) -> f32 {
+        (PATH_WIDTH / 2.0 - self.radius / 2.0).max(0.0)
+    }
+    
+    // Pick a lane as a fraction of the path half-width, -1 to 1
+    pub fn set_lane(&mut self, fraction: f32) {
+        self.lane_offset = fraction.clamp(-1.0, 1.0) * self.max_lateral();
+        self.lateral = self.lane_offset;
+    }
+    
+    // Move sideways by the crowd's push, drifting back towards the preferred
+    // lane. Progress along the path doesn't change.
+    pub fn steer_lateral(&mut self, push: f32, dt: f32) {
+        const LANE_RETURN_RATE: f32 = 1.5; // fraction of the gap closed per second
+        self.lateral += push * dt;
+        self.lateral += (self.lane_offset - self.lateral) * (LANE_RETURN_RATE * dt).min(1.0);
+        self.lateral = self.lateral.clamp(-self.max_lateral(), self.max_lateral());
+    }
+    
+    pub fn get_velocity(&self) -> (f32, f32) {
+        self.velocity
+    }
+    
+    pub fn is_flying(&self) -> bool {
+        self.flying
     }
     
     pub fn is_active(&self) -> bool {
@@ -0,0 +1,6 @@
+// Ways an enemy gets further along the path than walking would take it
+#[derive(Debug, Clone, Copy)]
+pub enum PathSkip {
+    Blink { distance: f32, cooldown: f32 },         // jumps ahead along the path
+    Burrow { surface_time: f32, burrow_time: f32 }, // untargetable while underground
+}
@@ -0,0 +1,13 @@
+// What happens when a boss drops below one of its health thresholds
+#[derive(Debug, Clone, Copy)]
+pub enum PhaseEffect {
+    SpeedUp(f32),       // speed multiplier
+    SpawnMinions(u32),  // fast enemies released at the boss's position
+    Shield(u32),        // shield points that soak up damage before health
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BossPhase {
+    pub health_fraction: f32, // triggers once health drops to this share of max
+    pub effect: PhaseEffect,
+}
@@ -0,0 +1,13 @@
+// What a siege enemy does to a tower it stops next to
+#[derive(Debug, Clone, Copy)]
+pub enum SiegeEffect {
+    Damage(u32), // knocked off the tower's health
+    Emp(f32),    // seconds the tower is disabled for
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SiegeAttack {
+    pub effect: SiegeEffect,
+    pub range: f32,
+    pub interval: f32, // seconds between attacks
+}
@@ -1,21 +1,51 @@
-use ggez::{Context, GameResult, graphics};
-use hecs::World;
+use ggez::{Context, GameError, GameResult, graphics};
+use hecs::{CommandBuffer, Entity, Ref, World};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
 use glam::Vec2;
 
-use crate::game::enemy::{Enemy, EnemyType};
+use crate::game::economy::EconomyRules;
+use crate::game::enemy::{Affix, Enemy, EnemyType, FormationKind, SiegeEffect, SupportAura};
+use crate::game::history::{BuildAction, BuildHistory};
 use crate::game::map::{Map, MapType};
+use crate::game::menu::DifficultyLevel;
 use crate::game::player::Player;
-use crate::game::tower::{Tower, TowerType};
+use crate::game::power::{PowerGrid, PowerNode};
+use crate::game::powerup::{Powerup, PowerupType};
+use crate::game::tower::{DamageType, PathEffect, Shot, TargetInfo, TargetLayers, Tower, TowerType};
+use crate::game::wave::{WaveScript, WaveUnit};
+
+const GAME_SEED: u64 = 0x5eed; // same seed, same elite rolls
+const SHOT_RADIUS: f32 = 3.0;   // size of a bullet when checking it against enemy hitboxes
+const THREAT_WEIGHT: f32 = 0.02; // route cost per point of DPS per pixel of route it covers
 
 pub struct Game {
     world: World,
     player: Player,
     current_map: MapType,
+    waves: WaveScript, // the current map's wave schedule
     wave: u32,
     game_time: f32,
     wave_cooldown: f32,
     game_state: GameState,
     projectiles: Vec<Projectile>, // Add projectiles storage
+    difficulty: DifficultyLevel,
+    economy: EconomyRules,
+    build_history: BuildHistory,
+    merge_enabled: bool,
+    power_grid_enabled: bool,
+    power_grid: PowerGrid,
+    impacts: Vec<Impact>, // artillery shells still in the air
+    overkill_prevention: bool,
+    damage_fired: u64,  // damage carried by every shot fired
+    damage_wasted: u64, // damage that hit nothing or went past a kill
+    powerups: Vec<Powerup>, // bought and still running
+    rng: StdRng,
+    formations: Vec<Formation>, // groups still marching together
+    route_costs: Vec<f32>,      // length plus tower threat for each ground route
+    route_debug: bool,          // show route costs on the map
 }
 
 // Adding a Projectile struct to visualize tower shots
@@ -27,6 +57,76 @@ pub struct Projectile {
     color: [f32; 4],
 }
 
+// Snapshot of the boss on the field for the boss health bar
+pub struct BossStatus {
+    pub name: &'static str,
+    pub health: u32,
+    pub max_health: u32,
+    pub shield: u32,
+}
+
+// What the next wave will bring, for the wave preview
+pub struct WavePreview {
+    pub wave: u32,
+    pub enemies: Vec<(EnemyType, u32)>, // in order of first appearance
+    pub stealth: u32,
+    pub formation: Option<FormationKind>,
+}
+
+// Enemies that keep their place around a leader until the group breaks up
+struct Formation {
+    leader: Entity,
+    members: Vec<(Entity, f32)>, // member and how far ahead of the leader it marches
+    size: usize,                 // members at spawn
+}
+
+// What a shot does to each enemy it hits
+#[derive(Clone, Copy)]
+struct Payload {
+    damage: u32,
+    layers: TargetLayers,
+    damage_type: DamageType,
+    path_effect: Option<PathEffect>,
+}
+
+impl Payload {
+    fn from_shot(shot: &Shot) -> Self {
+        Self {
+            damage: shot.damage,
+            layers: shot.layers,
+            damage_type: shot.damage_type,
+            path_effect: shot.path_effect,
+        }
+    }
+    
+    // Damage the enemy and move survivors along the route. Returns the
+    // health and shield the enemy lost and whether it died.
+    fn hit(&self, enemy: &mut Enemy, map: &Map) -> (u32, bool) {
+        let (health_lost, shield_lost, killed) = enemy.take_damage(self.damage, self.damage_type);
+        if !killed {
+            match self.path_effect {
+                Some(PathEffect::Knockback(distance)) => enemy.knock_back(distance),
+                Some(PathEffect::Pull { toward, distance }) => {
+                    let progress = map.route_progress(enemy.get_route(), enemy.is_flying(), toward.0, toward.1);
+                    enemy.pull_towards(progress, distance);
+                }
+                None => {}
+            }
+        }
+        (health_lost + shield_lost, killed)
+    }
+}
+
+// Where and when an artillery shell lands
+struct Impact {
+    position: (f32, f32),
+    target: Option<Entity>, // enemy the shell was aimed at
+    payload: Payload,
+    splash_radius: f32,
+    time_left: f32,
+    tower: Entity, // credited with the hits
+}
+
 #[derive(Debug, Clone)]
 pub enum GameState {
     Playing,
@@ -39,17 +139,40 @@ impl Game {
     pub fn new(_ctx: &mut Context) -> GameResult<Self> {
         let world = World::new();
         let player = Player::new(100, 1000); // Changed from 50 to 1000 gold
+        let difficulty = DifficultyLevel::Normal;
+        let current_map = MapType::Beginner;
+        let waves = WaveScript::parse(Map::new(current_map).get_wave_script()).map_err(|err| {
+            GameError::ResourceLoadError(format!("wave file for the {:?} map, {}", current_map, err))
+        })?;
         
-        Ok(Self {
+        let mut game = Self {
             world,
             player,
-            current_map: MapType::Beginner,
+            current_map,
+            waves,
             wave: 0,
             game_time: 0.0,
             wave_cooldown: 5.0, // 5 seconds between waves
             game_state: GameState::Playing,
             projectiles: Vec::new(), // Initialize projectiles vector
-        })
+            difficulty,
+            economy: EconomyRules::for_difficulty(difficulty),
+            build_history: BuildHistory::new(),
+            merge_enabled: false,
+            power_grid_enabled: false,
+            power_grid: PowerGrid::new(),
+            impacts: Vec::new(),
+            overkill_prevention: false,
+            damage_fired: 0,
+            damage_wasted: 0,
+            powerups: Vec::new(),
+            rng: StdRng::seed_from_u64(GAME_SEED),
+            formations: Vec::new(),
+            route_costs: Vec::new(),
+            route_debug: false,
+        };
+        game.update_route_costs();
+        Ok(game)
     }
 
     pub fn update(&mut self, ctx: &mut Context) -> GameResult {
@@ -62,44 +185,120 @@ impl Game {
             if self.wave_cooldown > 0.0 {
                 self.wave_cooldown -= dt;
             } else {
-                self.spawn_wave();
-                self.wave += 1;
-                self.wave_cooldown = 20.0; // 20 seconds between waves
+                self.start_next_wave();
             }
             
+            self.update_power_grid();
+            self.apply_support_auras(dt);
+            self.powerups.retain_mut(|powerup| powerup.update(dt));
+            self.update_detection();
+            
             // Update towers (detect enemies, shoot)
             let mut targets = Vec::new();
             
+            // Shells still in the air count against the enemy they were aimed at
+            let mut incoming: HashMap<Entity, u32> = HashMap::new();
+            if self.overkill_prevention {
+                for impact in &self.impacts {
+                    if let Some(target) = impact.target {
+                        *incoming.entry(target).or_insert(0) += impact.payload.damage;
+                    }
+                }
+            }
+            
             // Collect enemy positions for tower targeting
-            let enemy_positions: Vec<(f32, f32)> = self.world.query::<&Enemy>()
+            let mut enemy_positions: Vec<TargetInfo> = self.world.query::<&Enemy>()
                 .iter()
-                .filter(|(_, enemy)| enemy.is_active())
-                .map(|(_, enemy)| enemy.get_position())
+                .filter(|(_, enemy)| enemy.is_active() && enemy.get_health() > 0)
+                .map(|(id, enemy)| TargetInfo {
+                    entity: id,
+                    position: enemy.get_position(),
+                    velocity: enemy.get_velocity(),
+                    health: enemy.get_health(),
+                    shield: enemy.get_shield(),
+                    pending_damage: incoming.get(&id).copied().unwrap_or(0),
+                    flying: enemy.is_flying(),
+                    hidden: enemy.is_hidden(),
+                    untargetable: enemy.is_untargetable(),
+                })
                 .collect();
             
             // Update towers and collect targets with damage information
-            for (_, tower) in self.world.query::<&mut Tower>().iter() {
-                if let Some(target) = tower.update(dt, &enemy_positions) {
+            for (tower_id, tower) in self.world.query::<&mut Tower>().iter() {
+                if let Some(shot) = tower.update(dt, &enemy_positions) {
+                    self.damage_fired += shot.damage as u64;
+                    
+                    // Let the towers after this one know the shot is on its way,
+                    // so they pick another enemy if it's going to be a kill
+                    if self.overkill_prevention
+                        && let Some(target) = enemy_positions.iter_mut().find(|enemy| Some(enemy.entity) == shot.target_entity)
+                    {
+                        target.pending_damage += shot.damage;
+                    }
+                    
+                    // Piercing shots fly on to the edge of the tower's range
+                    let start = Vec2::new(tower.get_position().0, tower.get_position().1);
+                    let mut end = Vec2::new(shot.target.0, shot.target.1);
+                    if shot.piercing {
+                        end = start + (end - start).normalize_or_zero() * tower.get_range();
+                    }
+                    
                     // Create a projectile when tower fires
                     let projectile = Projectile {
-                        start: Vec2::new(tower.get_position().0, tower.get_position().1),
-                        target: Vec2::new(target.0, target.1),
+                        start,
+                        target: end,
                         time_alive: 0.0,
-                        max_lifetime: 0.5, // Half a second lifetime for projectile
+                        // Half a second lifetime for projectile, shells fly until they land
+                        max_lifetime: shot.shell.map_or(0.5, |shell| shell.flight_time),
                         color: match tower.get_tower_type() {
                             TowerType::Basic => [0.2, 0.6, 0.8, 1.0],
                             TowerType::Advanced => [0.8, 0.2, 0.8, 1.0],
+                            TowerType::Generator => [0.9, 0.8, 0.1, 1.0],
+                            TowerType::Mortar => [0.9, 0.5, 0.1, 1.0],
+                            TowerType::Detector => [0.3, 0.9, 0.9, 1.0],
+                            TowerType::Gravity => [0.5, 0.3, 0.8, 1.0],
+                            TowerType::Flak => [0.9, 0.4, 0.2, 1.0],
                         },
                     };
                     self.projectiles.push(projectile);
                     
-                    targets.push((target, tower.get_damage()));
+                    match shot.shell {
+                        Some(shell) => self.impacts.push(Impact {
+                            position: shot.target,
+                            target: shot.target_entity,
+                            payload: Payload::from_shot(&shot),
+                            splash_radius: shell.splash_radius,
+                            time_left: shell.flight_time,
+                            tower: tower_id,
+                        }),
+                        None => targets.push((tower_id, shot, start, end)),
+                    }
                 }
             }
             
-            // Apply damage to enemies that were hit by tower shots
-            for (target_pos, damage) in targets {
-                self.damage_enemy_at_position(target_pos, damage);
+            // Apply damage to enemies that were hit by tower shots and credit
+            // the tower that fired
+            for (tower_id, shot, start, end) in targets {
+                let hits = if shot.piercing {
+                    self.damage_enemies_along_line(start, end, Payload::from_shot(&shot))
+                } else {
+                    self.damage_enemy_at_position(shot.target, Payload::from_shot(&shot))
+                };
+                
+                self.credit_hits(tower_id, shot.damage, hits);
+            }
+            
+            // Resolve artillery shells that have landed
+            for impact in &mut self.impacts {
+                impact.time_left -= dt;
+            }
+            let (landed, in_flight): (Vec<Impact>, Vec<Impact>) = std::mem::take(&mut self.impacts)
+                .into_iter()
+                .partition(|impact| impact.time_left <= 0.0);
+            self.impacts = in_flight;
+            for impact in landed {
+                let hits = self.damage_enemies_in_radius(impact.position, impact.splash_radius, impact.payload);
+                self.credit_hits(impact.tower, impact.payload.damage, hits);
             }
             
             // Update existing projectiles
@@ -113,6 +312,11 @@ impl Game {
                 }
             }
             
+            self.update_formations();
+            self.update_route_costs();
+            self.route_enemies();
+            self.update_siege(dt);
+            
             // Update enemies (movement, take damage)
             let map = self.get_current_map();
             let mut enemies_to_remove = Vec::new();
@@ -120,7 +324,20 @@ impl Game {
             let mut rewards_earned = 0;
             let mut score_earned = 0;
             
+            // Enemies spawned during the loop are queued and added once the
+            // query is done, so they first move next tick
+            let mut spawns = CommandBuffer::new();
+            let mut deaths = Vec::new();
+            
             for (id, enemy) in self.world.query::<&mut Enemy>().iter() {
+                // Release minions from bosses that changed phase
+                let minions = enemy.take_pending_minions();
+                if enemy.get_health() > 0 {
+                    for _ in 0..minions {
+                        spawns.spawn((enemy.spawn_at(EnemyType::Fast),));
+                    }
+                }
+                
                 if enemy.update(dt, &map) {
                     // Enemy reached the end of path
                     player_health_deduction += enemy.get_damage();
@@ -130,9 +347,26 @@ impl Game {
                     rewards_earned += enemy.get_reward();
                     score_earned += enemy.get_reward() * 10;
                     enemies_to_remove.push(id);
+                    
+                    let (x, y) = enemy.get_position();
+                    deaths.push((Vec2::new(x, y), enemy.get_max_health(), enemy.has_affix(Affix::Explosive)));
+                    
+                    // Splitters break up into children that carry on from here
+                    for child in enemy.spawn_children() {
+                        spawns.spawn((child,));
+                    }
                 }
             }
             
+            spawns.run_on(&mut self.world);
+            self.resolve_elite_deaths(&deaths);
+            self.separate_enemies(dt);
+            
+            // Remove enemies that were killed or leaked so they only count once
+            for id in enemies_to_remove {
+                let _ = self.world.despawn(id);
+            }
+            
             // Apply damage and rewards
             if player_health_deduction > 0 {
                 self.player.take_damage(player_health_deduction);
@@ -147,7 +381,7 @@ impl Game {
             }
             
             // Check for victory condition
-            if self.wave >= 20 && self.world.query::<&Enemy>().iter().count() == 0 {
+            if self.wave >= self.waves.wave_count() && self.world.query::<&Enemy>().iter().count() == 0 {
                 self.game_state = GameState::Victory;
             }
         }
@@ -157,7 +391,17 @@ impl Game {
 
     pub fn draw(&self, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult {
         // Draw the map
-        self.get_current_map().draw(ctx, canvas)?;
+        let map = self.get_current_map();
+        map.draw(ctx, canvas)?;
+        
+        if self.route_debug {
+            self.draw_route_costs(&map, ctx, canvas)?;
+        }
+        
+        // Draw power links between generators and the towers they feed
+        if self.power_grid_enabled {
+            self.power_grid.draw(ctx, canvas)?;
+        }
         
         // Draw all towers
         for (_id, tower) in self.world.query::<&Tower>().iter() {
@@ -169,6 +413,19 @@ impl Game {
             enemy.draw(ctx, canvas)?;
         }
         
+        // Mark where artillery shells are about to land
+        for impact in &self.impacts {
+            let marker = graphics::Mesh::new_circle(
+                ctx,
+                graphics::DrawMode::stroke(1.5),
+                Vec2::new(impact.position.0, impact.position.1),
+                impact.splash_radius,
+                0.5,
+                [1.0, 0.3, 0.1, 0.6].into(),
+            )?;
+            canvas.draw(&marker, graphics::DrawParam::default());
+        }
+        
         // Draw projectiles
         for projectile in &self.projectiles {
             // Calculate position along trajectory based on lifetime
@@ -204,26 +461,435 @@ impl Game {
         Ok(())
     }
 
-    // Fix the implementation by moving this function inside the impl block
-    fn damage_enemy_at_position(&mut self, position: (f32, f32), damage: u32) {
-        // Find enemies close to the target position (with some tolerance)
-        const HIT_TOLERANCE: f32 = 15.0; // Pixels of tolerance for hit detection
+    // Returns the damage actually dealt to each enemy struck and whether it died,
+    // so every hit is credited on its own
+    fn damage_enemy_at_position(&mut self, position: (f32, f32), payload: Payload) -> Vec<(u32, bool)> {
+        // Find enemies whose hitbox the shot lands in
+        let map = self.get_current_map();
+        let mut hits = Vec::new();
         
         for (_, enemy) in self.world.query::<&mut Enemy>().iter() {
+            // Dead enemies are removed at the end of the tick; don't hit them twice
+            if !enemy.is_active() || enemy.get_health() == 0 || enemy.is_untargetable() || !payload.layers.can_hit(enemy.is_flying()) {
+                continue;
+            }
+            
             let enemy_pos = enemy.get_position();
             let dx = enemy_pos.0 - position.0;
             let dy = enemy_pos.1 - position.1;
             let distance_squared = dx * dx + dy * dy;
+            let reach = enemy.get_radius() + SHOT_RADIUS;
             
-            // If enemy is close enough to the target point, damage it
-            if distance_squared <= HIT_TOLERANCE * HIT_TOLERANCE {
-                if enemy.take_damage(damage) {
+            // If the shot overlaps the enemy, damage it
+            if distance_squared <= reach * reach {
+                let (dealt, killed) = payload.hit(enemy, &map);
+                hits.push((dealt, killed));
+                if killed {
                     // Enemy was killed by this shot, score and rewards will be collected
                     // in the main update loop
                     break;
                 }
             }
         }
+        
+        hits
+    }
+
+    // Work out how much power each tower gets this tick
+    fn update_power_grid(&mut self) {
+        if !self.power_grid_enabled {
+            for (_, tower) in self.world.query::<&mut Tower>().iter() {
+                tower.set_power_ratio(1.0);
+            }
+            return;
+        }
+        
+        let nodes: Vec<PowerNode> = self.world.query::<&Tower>()
+            .iter()
+            .map(|(entity, tower)| PowerNode {
+                entity,
+                position: tower.get_position(),
+                demand: tower.get_power_demand(),
+                output: tower.get_power_output(),
+                radius: tower.get_grid_radius(),
+            })
+            .collect();
+        
+        self.power_grid = PowerGrid::compute(&nodes);
+        
+        for (entity, tower) in self.world.query::<&mut Tower>().iter() {
+            if !tower.is_generator() {
+                tower.set_power_ratio(self.power_grid.ratio_for(entity));
+            }
+        }
+    }
+
+    // Healers and wardens help every other enemy within their aura
+    fn apply_support_auras(&mut self, dt: f32) {
+        let sources: Vec<(Entity, Vec2, f32, SupportAura)> = self.world.query::<&Enemy>()
+            .iter()
+            .filter(|(_, enemy)| enemy.is_active() && enemy.get_health() > 0)
+            .filter_map(|(entity, enemy)| {
+                let (aura, radius) = enemy.get_aura()?;
+                let (x, y) = enemy.get_position();
+                Some((entity, Vec2::new(x, y), radius, aura))
+            })
+            .collect();
+        
+        if sources.is_empty() {
+            return;
+        }
+        
+        for (entity, enemy) in self.world.query::<&mut Enemy>().iter() {
+            if !enemy.is_active() || enemy.get_health() == 0 {
+                continue;
+            }
+            
+            let (x, y) = enemy.get_position();
+            let position = Vec2::new(x, y);
+            for (source, center, radius, aura) in &sources {
+                if *source == entity || position.distance(*center) > *radius {
+                    continue;
+                }
+                
+                match *aura {
+                    SupportAura::Heal { per_second } => enemy.heal(per_second * dt),
+                    SupportAura::Armor { amount, duration } => enemy.grant_armor(amount, duration),
+                }
+            }
+        }
+    }
+
+    // Siege enemies stop next to the closest tower in reach and attack it.
+    // EMP enemies skip towers that are already disabled.
+    fn update_siege(&mut self, dt: f32) {
+        let towers: Vec<(Entity, Vec2, bool)> = self.world.query::<&Tower>()
+            .iter()
+            .map(|(entity, tower)| {
+                let (x, y) = tower.get_position();
+                (entity, Vec2::new(x, y), tower.is_stunned())
+            })
+            .collect();
+        
+        let mut attacks = Vec::new();
+        for (_, enemy) in self.world.query::<&mut Enemy>().iter() {
+            let Some(siege) = enemy.get_siege() else {
+                continue;
+            };
+            if !enemy.is_active() || enemy.get_health() == 0 {
+                continue;
+            }
+            
+            let (x, y) = enemy.get_position();
+            let position = Vec2::new(x, y);
+            let emp = matches!(siege.effect, SiegeEffect::Emp(_));
+            // Nothing is in reach from underground
+            let target = if enemy.is_burrowed() {
+                None
+            } else {
+                towers.iter()
+                    .filter(|(_, tower_position, stunned)| !(emp && *stunned) && tower_position.distance(position) <= siege.range)
+                    .min_by(|a, b| a.1.distance(position).total_cmp(&b.1.distance(position)))
+                    .map(|(entity, ..)| *entity)
+            };
+            
+            if let (Some(effect), Some(target)) = (enemy.update_siege(dt, target.is_some()), target) {
+                attacks.push((target, effect));
+            }
+        }
+        
+        for (target, effect) in attacks {
+            let destroyed = match self.world.get::<&mut Tower>(target) {
+                Ok(mut tower) => match effect {
+                    SiegeEffect::Damage(damage) => tower.take_damage(damage),
+                    SiegeEffect::Emp(duration) => {
+                        tower.stun(duration);
+                        false
+                    }
+                },
+                Err(_) => false,
+            };
+            if destroyed {
+                // A destroyed tower can't be refunded or brought back by undo
+                let _ = self.world.despawn(target);
+                self.build_history.forget(target);
+            }
+        }
+    }
+
+    // Price each ground route by its length plus how much tower fire covers
+    // it. Recomputed every tick, so power, stuns, ranks and abilities count
+    // as soon as they change.
+    fn update_route_costs(&mut self) {
+        const SAMPLE_STEP: f32 = 10.0;
+        let map = self.get_current_map();
+        let towers: Vec<(Vec2, f32, f32, f32)> = self.world.query::<&Tower>()
+            .iter()
+            .filter(|(_, tower)| tower.get_tower_type().target_layers().can_hit(false))
+            .map(|(_, tower)| {
+                let (x, y) = tower.get_position();
+                (Vec2::new(x, y), tower.get_min_range(), tower.get_range(), tower.get_dps())
+            })
+            .filter(|(_, _, _, dps)| *dps > 0.0)
+            .collect();
+        
+        self.route_costs = (0..map.route_count())
+            .map(|route| {
+                let length = map.route_length(route);
+                let mut threat = 0.0;
+                let mut distance = 0.0;
+                while let Some(((x, y), _)) = map.route_point(route, false, distance) {
+                    let point = Vec2::new(x, y);
+                    for (position, min_range, range, dps) in &towers {
+                        let dist = point.distance(*position);
+                        if dist >= *min_range && dist <= *range {
+                            threat += dps * SAMPLE_STEP;
+                        }
+                    }
+                    distance += SAMPLE_STEP;
+                }
+                length + threat * THREAT_WEIGHT
+            })
+            .collect();
+    }
+
+    // Ground enemies take the cheapest route that still runs through the
+    // spot they're on, so each junction is decided as they reach it
+    fn route_enemies(&mut self) {
+        if self.route_costs.len() < 2 {
+            return;
+        }
+        
+        // A formation only turns off while its front-most member hasn't
+        // passed the split, so it goes by the furthest progress of the group
+        let mut fronts: HashMap<Entity, f32> = HashMap::new();
+        for formation in &self.formations {
+            let front = std::iter::once(formation.leader)
+                .chain(formation.members.iter().map(|&(entity, _)| entity))
+                .filter_map(|entity| self.world.get::<&Enemy>(entity).ok().map(|enemy| enemy.get_progress()))
+                .fold(0.0, f32::max);
+            fronts.insert(formation.leader, front);
+        }
+        
+        let map = self.get_current_map();
+        for (id, enemy) in self.world.query::<&mut Enemy>().iter() {
+            if enemy.is_flying() || enemy.is_in_formation() {
+                continue;
+            }
+            
+            let current = enemy.get_route();
+            let progress = fronts.get(&id).copied().unwrap_or(enemy.get_progress());
+            let best = (0..self.route_costs.len())
+                .filter(|&route| route == current || map.shared_length(current, route) >= progress)
+                .min_by(|&a, &b| self.route_costs[a].total_cmp(&self.route_costs[b]))
+                .unwrap_or(current);
+            if self.route_costs[best] < self.route_costs[current] {
+                enemy.set_route(best);
+            }
+        }
+    }
+
+    // Label each route with its cost where it runs on its own
+    fn draw_route_costs(&self, map: &Map, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult {
+        let cheapest = (0..self.route_costs.len())
+            .min_by(|&a, &b| self.route_costs[a].total_cmp(&self.route_costs[b]));
+        
+        for (route, cost) in self.route_costs.iter().enumerate() {
+            let split = (0..map.route_count())
+                .filter(|&other| other != route)
+                .map(|other| map.shared_length(route, other))
+                .fold(0.0, f32::max);
+            let Some(((x, y), _)) = map.route_point(route, false, (split + map.route_length(route)) / 2.0) else {
+                continue;
+            };
+            
+            let marker = graphics::Mesh::new_circle(
+                ctx,
+                graphics::DrawMode::fill(),
+                Vec2::new(x, y),
+                4.0,
+                0.1,
+                [1.0, 1.0, 1.0, 0.8].into(),
+            )?;
+            canvas.draw(&marker, graphics::DrawParam::default());
+            
+            let label = graphics::Text::new(format!("Route {}: {:.0}", route + 1, cost));
+            canvas.draw(
+                &label,
+                graphics::DrawParam::default()
+                    .dest(Vec2::new(x + 6.0, y - 18.0))
+                    .color(if Some(route) == cheapest {
+                        [0.4f32, 1.0f32, 0.4f32, 1.0f32]
+                    } else {
+                        [1.0f32, 1.0f32, 1.0f32, 1.0f32]
+                    }),
+            );
+        }
+        
+        Ok(())
+    }
+
+    // Push overlapping enemies apart sideways so crowds spread across the path
+    fn separate_enemies(&mut self, dt: f32) {
+        const SEPARATION_STRENGTH: f32 = 6.0; // sideways speed per pixel of overlap
+        
+        let crowd: Vec<(Entity, Vec2, f32, bool)> = self.world.query::<&Enemy>()
+            .iter()
+            .filter(|(_, enemy)| enemy.is_active() && enemy.get_health() > 0)
+            .map(|(entity, enemy)| {
+                let (x, y) = enemy.get_position();
+                (entity, Vec2::new(x, y), enemy.get_radius(), enemy.is_flying())
+            })
+            .collect();
+        
+        for (entity, enemy) in self.world.query::<&mut Enemy>().iter() {
+            let Some(&(_, position, radius, flying)) = crowd.iter().find(|(id, ..)| *id == entity) else {
+                continue;
+            };
+            let (hx, hy) = enemy.get_heading();
+            let side = Vec2::new(-hy, hx);
+            
+            let mut push = 0.0;
+            for &(other, other_position, other_radius, other_flying) in &crowd {
+                if other == entity || other_flying != flying {
+                    continue;
+                }
+                
+                let overlap = radius + other_radius - position.distance(other_position);
+                if overlap <= 0.0 {
+                    continue;
+                }
+                
+                // Step away from the side the other enemy is on; exact ties are
+                // broken by entity id so the result doesn't depend on float noise
+                let across = (other_position - position).dot(side);
+                let away = if across.abs() > f32::EPSILON {
+                    -across.signum()
+                } else if entity.id() < other.id() {
+                    -1.0
+                } else {
+                    1.0
+                };
+                push += away * overlap * SEPARATION_STRENGTH;
+            }
+            
+            enemy.steer_lateral(push, dt);
+        }
+    }
+
+    // Explosive elites stun towers around them and vampiric elites feed on the dead
+    fn resolve_elite_deaths(&mut self, deaths: &[(Vec2, u32, bool)]) {
+        for &(position, max_health, explosive) in deaths {
+            if explosive {
+                for (_, tower) in self.world.query::<&mut Tower>().iter() {
+                    let (x, y) = tower.get_position();
+                    if position.distance(Vec2::new(x, y)) <= Affix::EXPLOSION_RADIUS {
+                        tower.stun(Affix::STUN_DURATION);
+                    }
+                }
+            }
+            
+            for (_, enemy) in self.world.query::<&mut Enemy>().iter() {
+                if !enemy.has_affix(Affix::Vampiric) {
+                    continue;
+                }
+                let (x, y) = enemy.get_position();
+                if position.distance(Vec2::new(x, y)) <= Affix::VAMPIRIC_RADIUS {
+                    enemy.heal(max_health as f32 * Affix::VAMPIRIC_DRAIN);
+                }
+            }
+        }
+    }
+
+    // Reveal stealth enemies inside any detection source
+    fn update_detection(&mut self) {
+        let reveal_all = self.is_powerup_active(PowerupType::Reveal);
+        let sources: Vec<(Vec2, f32)> = self.world.query::<&Tower>()
+            .iter()
+            .filter(|(_, tower)| tower.get_detection_radius() > 0.0)
+            .map(|(_, tower)| (Vec2::new(tower.get_position().0, tower.get_position().1), tower.get_detection_radius()))
+            .collect();
+        
+        for (_, enemy) in self.world.query::<&mut Enemy>().iter() {
+            if !enemy.is_stealthy() {
+                continue;
+            }
+            
+            let (x, y) = enemy.get_position();
+            let position = Vec2::new(x, y);
+            enemy.set_detected(reveal_all || sources.iter().any(|(center, radius)| position.distance(*center) <= *radius));
+        }
+    }
+
+    // Credit the tower with its hits and track how much of the shot was wasted
+    fn credit_hits(&mut self, tower_id: Entity, damage: u32, hits: Vec<(u32, bool)>) {
+        if hits.iter().all(|(dealt, _)| *dealt == 0) {
+            self.damage_wasted += damage as u64;
+        } else {
+            self.damage_wasted += hits.iter()
+                .filter(|(_, killed)| *killed)
+                .map(|(dealt, _)| damage.saturating_sub(*dealt) as u64)
+                .sum::<u64>();
+        }
+        
+        let mut fought = false;
+        if let Ok(mut tower) = self.world.get::<&mut Tower>(tower_id) {
+            for (dealt, killed) in hits.into_iter().filter(|(dealt, _)| *dealt > 0) {
+                tower.record_hit(dealt, killed);
+                fought = true;
+            }
+        }
+        
+        // A tower that has already earned kills, gold or XP can't be taken
+        // back for a full refund
+        if fought {
+            self.build_history.forget(tower_id);
+        }
+    }
+
+    // Damage every living enemy within a splash radius
+    fn damage_enemies_in_radius(&mut self, center: (f32, f32), radius: f32, payload: Payload) -> Vec<(u32, bool)> {
+        let map = self.get_current_map();
+        let mut hits = Vec::new();
+        
+        for (_, enemy) in self.world.query::<&mut Enemy>().iter() {
+            if !enemy.is_active() || enemy.get_health() == 0 || enemy.is_untargetable() || !payload.layers.can_hit(enemy.is_flying()) {
+                continue;
+            }
+            
+            let enemy_pos = enemy.get_position();
+            let dx = enemy_pos.0 - center.0;
+            let dy = enemy_pos.1 - center.1;
+            // Anything the blast overlaps is caught, not just enemy centres
+            let reach = radius + enemy.get_radius();
+            if dx * dx + dy * dy <= reach * reach {
+                hits.push(payload.hit(enemy, &map));
+            }
+        }
+        
+        hits
+    }
+
+    // Damage every living enemy close to the line between two points
+    fn damage_enemies_along_line(&mut self, start: Vec2, end: Vec2, payload: Payload) -> Vec<(u32, bool)> {
+        let map = self.get_current_map();
+        let along = end - start;
+        let mut hits = Vec::new();
+        
+        for (_, enemy) in self.world.query::<&mut Enemy>().iter() {
+            if !enemy.is_active() || enemy.get_health() == 0 || enemy.is_untargetable() || !payload.layers.can_hit(enemy.is_flying()) {
+                continue;
+            }
+            
+            let enemy_pos = Vec2::new(enemy.get_position().0, enemy.get_position().1);
+            let t = ((enemy_pos - start).dot(along) / along.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+            let reach = enemy.get_radius() + SHOT_RADIUS;
+            if (start + along * t).distance_squared(enemy_pos) <= reach * reach {
+                hits.push(payload.hit(enemy, &map));
+            }
+        }
+        
+        hits
     }
 
     // Add a helper method to get the tower type
@@ -231,29 +897,169 @@ impl Game {
         Map::new(self.current_map)
     }
 
-    fn spawn_wave(&mut self) {
-        let num_enemies = 5 + self.wave as usize;
-        let enemy_type = if self.wave % 5 == 0 {
-            EnemyType::Boss
-        } else if self.wave % 3 == 0 {
-            EnemyType::Fast
-        } else {
-            EnemyType::Normal
-        };
+    fn start_next_wave(&mut self) {
+        // Interest is paid on whatever gold is still banked when the wave starts
+        let interest = self.economy.interest_on(self.player.get_gold());
+        if interest > 0 {
+            self.player.add_gold(interest);
+        }
+        
+        // Everything built during the build phase is final once the wave starts
+        self.build_history.lock();
+        
+        self.spawn_wave();
+        self.wave += 1;
+        self.wave_cooldown = 20.0; // 20 seconds between waves
+    }
+
+    // Skip the rest of the cooldown and get paid for the time skipped
+    pub fn call_next_wave(&mut self) -> bool {
+        if !matches!(self.game_state, GameState::Playing) ||
+           !self.economy.is_early_call_enabled() ||
+           self.wave_cooldown <= 0.0 {
+            return false;
+        }
         
+        let bonus = self.get_early_call_bonus();
+        self.start_next_wave();
+        if bonus > 0 {
+            self.player.add_gold(bonus);
+        }
+        true
+    }
+
+    // Spawn the groups the wave file lists for the wave about to start
+    fn spawn_wave(&mut self) {
         let map = self.get_current_map();
         let start_pos = map.get_path_start();
+        let groups = self.waves.get_wave(self.wave + 1).to_vec();
         
-        for i in 0..num_enemies {
-            let enemy = Enemy::new(
-                enemy_type,
-                start_pos.0, 
-                start_pos.1,
-                2.0 + (i as f32 * 0.5), // Delay for each enemy
-            );
-            self.world.spawn((enemy,));
+        for group in groups {
+            for i in 0..group.count {
+                let spawn_delay = group.delay + i as f32 * group.interval;
+                match group.unit {
+                    WaveUnit::Enemy(enemy_type) => {
+                        let mut enemy = Enemy::new(enemy_type, start_pos.0, start_pos.1, spawn_delay);
+                        enemy.set_progress(group.spawn_point);
+                        enemy.set_lane(self.rng.gen_range(-1.0..=1.0));
+                        for affix in &group.affixes {
+                            enemy.add_affix(*affix);
+                        }
+                        self.roll_affixes(&mut enemy);
+                        self.world.spawn((enemy,));
+                    }
+                    WaveUnit::Formation(kind) => {
+                        self.spawn_formation(kind, start_pos, group.spawn_point, spawn_delay);
+                    }
+                }
+            }
+        }
+    }
+
+    fn spawn_formation(&mut self, kind: FormationKind, start_pos: (f32, f32), spawn_point: f32, spawn_delay: f32) {
+        let mut leader = Enemy::new(kind.leader(), start_pos.0, start_pos.1, spawn_delay);
+        leader.set_lane(0.0);
+        leader.set_progress(spawn_point);
+        let leader = self.world.spawn((leader,));
+        
+        let members: Vec<(Entity, f32)> = kind.slots()
+            .into_iter()
+            .map(|slot| {
+                let mut enemy = Enemy::new(slot.enemy_type, start_pos.0, start_pos.1, spawn_delay);
+                enemy.set_lane(slot.lane);
+                enemy.set_progress(spawn_point + slot.along);
+                (self.world.spawn((enemy,)), slot.along)
+            })
+            .collect();
+        
+        self.formations.push(Formation { leader, size: members.len(), members });
+    }
+
+    // Keep formation members in their slots around the leader. A formation
+    // breaks up once its leader falls or half of its members are gone.
+    fn update_formations(&mut self) {
+        let living = |world: &World, entity: Entity| {
+            world.get::<&Enemy>(entity).is_ok_and(|enemy| enemy.get_health() > 0)
+        };
+        
+        for formation in &mut self.formations {
+            formation.members.retain(|&(entity, _)| living(&self.world, entity));
+        }
+        
+        let (broken, intact): (Vec<Formation>, Vec<Formation>) = std::mem::take(&mut self.formations)
+            .into_iter()
+            .partition(|formation| {
+                !living(&self.world, formation.leader) || formation.members.len() * 2 < formation.size
+            });
+        self.formations = intact;
+        
+        for formation in broken {
+            for (entity, _) in formation.members {
+                if let Ok(mut enemy) = self.world.get::<&mut Enemy>(entity) {
+                    enemy.leave_formation();
+                }
+            }
+        }
+        
+        for formation in &self.formations {
+            let Ok(leader) = self.world.get::<&Enemy>(formation.leader) else {
+                continue;
+            };
+            let (route, progress, pace) = (leader.get_route(), leader.get_progress(), leader.get_pace());
+            drop(leader);
+            
+            for &(entity, along) in &formation.members {
+                if let Ok(mut enemy) = self.world.get::<&mut Enemy>(entity) {
+                    enemy.hold_formation(route, progress + along, pace);
+                }
+            }
+        }
+    }
+
+    // From wave 10 regular enemies may turn up as elites, more often and
+    // with more affixes as the waves go on
+    fn roll_affixes(&mut self, enemy: &mut Enemy) {
+        let regular = !matches!(
+            enemy.get_enemy_type(),
+            EnemyType::Boss | EnemyType::Healer | EnemyType::Warden | EnemyType::Splitling
+        );
+        if !regular || self.wave < 10 {
+            return;
+        }
+        
+        let chance = (0.05 * (self.wave - 9) as f64).min(0.5);
+        if !self.rng.gen_bool(chance) {
+            return;
+        }
+        
+        let count = 1 + (self.wave as usize - 10) / 10;
+        for affix in Affix::ALL.choose_multiple(&mut self.rng, count) {
+            enemy.add_affix(*affix);
         }
     }
+
+    pub fn get_wave_preview(&self) -> WavePreview {
+        let wave = self.wave + 1;
+        let groups = self.waves.get_wave(wave);
+        let formation = groups.iter().find_map(|group| match group.unit {
+            WaveUnit::Formation(kind) => Some(kind),
+            WaveUnit::Enemy(_) => None,
+        });
+        
+        let mut enemies: Vec<(EnemyType, u32)> = Vec::new();
+        for enemy_type in groups.iter().flat_map(|group| group.enemy_types()) {
+            match enemies.iter_mut().find(|(existing, _)| *existing == enemy_type) {
+                Some((_, count)) => *count += 1,
+                None => enemies.push((enemy_type, 1)),
+            }
+        }
+        let stealth = enemies.iter()
+            .filter(|(enemy_type, _)| *enemy_type == EnemyType::Stealth)
+            .map(|(_, count)| count)
+            .sum();
+        
+        WavePreview { wave, enemies, stealth, formation }
+    }
     
     pub fn get_player(&self) -> &Player {
         &self.player
@@ -267,6 +1073,53 @@ impl Game {
         &self.game_state
     }
     
+    // The active boss closest to death, if any
+    pub fn get_boss_status(&self) -> Option<BossStatus> {
+        self.world.query::<&Enemy>()
+            .iter()
+            .filter(|(_, enemy)| enemy.is_boss() && enemy.is_active() && enemy.get_health() > 0)
+            .min_by_key(|(_, enemy)| enemy.get_health())
+            .map(|(_, enemy)| BossStatus {
+                name: enemy.get_name(),
+                health: enemy.get_health(),
+                max_health: enemy.get_max_health(),
+                shield: enemy.get_shield(),
+            })
+    }
+    
+    pub fn get_wave_cooldown(&self) -> f32 {
+        self.wave_cooldown.max(0.0)
+    }
+    
+    pub fn get_projected_interest(&self) -> u32 {
+        self.economy.interest_on(self.player.get_gold())
+    }
+    
+    pub fn get_early_call_bonus(&self) -> u32 {
+        self.economy.early_call_bonus(self.wave_cooldown)
+    }
+    
+    pub fn get_difficulty(&self) -> DifficultyLevel {
+        self.difficulty
+    }
+    
+    // Switches to the difficulty's rules, including whether duplicate
+    // pricing starts on
+    pub fn set_difficulty(&mut self, difficulty: DifficultyLevel) {
+        self.difficulty = difficulty;
+        self.economy = EconomyRules::for_difficulty(difficulty);
+    }
+    
+    // Turn rising prices for duplicate towers on or off for this game
+    pub fn toggle_duplicate_pricing(&mut self) {
+        let enabled = self.economy.is_duplicate_pricing_enabled();
+        self.economy.set_duplicate_pricing(!enabled);
+    }
+    
+    pub fn is_duplicate_pricing_enabled(&self) -> bool {
+        self.economy.is_duplicate_pricing_enabled()
+    }
+    
     pub fn toggle_pause(&mut self) {
         self.game_state = match &self.game_state {
             GameState::Playing => GameState::Paused,
@@ -275,19 +1128,303 @@ impl Game {
         };
     }
 
+    // Current price of a tower type, which can rise with each copy owned
+    pub fn get_tower_price(&self, tower_type: TowerType) -> u32 {
+        let owned = self.world.query::<&Tower>()
+            .iter()
+            .filter(|(_, tower)| tower.get_tower_type() == tower_type)
+            .count() as u32;
+        self.economy.tower_price(tower_type.cost(), owned)
+    }
+
     pub fn place_tower(&mut self, tower_type: TowerType, x: f32, y: f32) -> bool {
-        let tower_cost = tower_type.cost();
+        let tower_cost = self.get_tower_price(tower_type);
         
         // Check if player has enough gold and position is valid
         if self.player.get_gold() >= tower_cost && 
            self.get_current_map().can_place_tower(x, y) {
             
-            let tower = Tower::new(tower_type, x, y);
-            self.world.spawn((tower,));
+            let mut tower = Tower::new(tower_type, x, y);
+            tower.face_towards(self.get_current_map().nearest_path_point(x, y));
+            let entity = self.world.spawn((tower,));
             self.player.spend_gold(tower_cost);
+            self.build_history.record(BuildAction::Place { entity, cost: tower_cost });
             return true;
         } else {
             return false;
         }
     }
+
+    pub fn tower_at(&self, x: f32, y: f32) -> Option<Entity> {
+        self.world.query::<&Tower>()
+            .iter()
+            .find(|(_, tower)| tower.contains_point(x, y))
+            .map(|(id, _)| id)
+    }
+
+    pub fn get_tower(&self, entity: Entity) -> Option<Ref<'_, Tower>> {
+        self.world.get::<&Tower>(entity).ok()
+    }
+
+    pub fn upgrade_tower(&mut self, entity: Entity) -> bool {
+        let Ok(mut tower) = self.world.get::<&mut Tower>(entity) else {
+            return false;
+        };
+        
+        let upgrade_cost = tower.get_upgrade_cost();
+        if self.player.get_gold() < upgrade_cost {
+            return false;
+        }
+        
+        let snapshot = Box::new(tower.clone());
+        tower.upgrade();
+        drop(tower);
+        
+        self.player.spend_gold(upgrade_cost);
+        self.build_history.record(BuildAction::Upgrade { entity, snapshot, cost: upgrade_cost });
+        true
+    }
+
+    // Pay to bring a damaged tower back to full health
+    pub fn repair_tower(&mut self, entity: Entity) -> bool {
+        let Ok(mut tower) = self.world.get::<&mut Tower>(entity) else {
+            return false;
+        };
+        let cost = tower.get_repair_cost();
+        if cost == 0 || self.player.get_gold() < cost {
+            return false;
+        }
+        
+        tower.repair();
+        drop(tower);
+        self.player.spend_gold(cost);
+        true
+    }
+
+    pub fn sell_tower(&mut self, entity: Entity) -> bool {
+        let Some(tower) = self.get_tower(entity).map(|tower| Tower::clone(&tower)) else {
+            return false;
+        };
+        
+        let refund = tower.get_sell_value();
+        let _ = self.world.despawn(entity);
+        self.player.add_gold(refund);
+        self.build_history.record(BuildAction::Sell { entity, tower: Box::new(tower), refund });
+        true
+    }
+
+    // Merge `source` into `target` when merging is on and the rules for their
+    // type allow it; the source tower's slot is freed
+    pub fn merge_towers(&mut self, source: Entity, target: Entity) -> bool {
+        if !self.merge_enabled || source == target {
+            return false;
+        }
+        
+        let Some(source_tower) = self.get_tower(source).map(|tower| Tower::clone(&tower)) else {
+            return false;
+        };
+        let Ok(mut target_tower) = self.world.get::<&mut Tower>(target) else {
+            return false;
+        };
+        if !target_tower.can_merge_with(&source_tower) {
+            return false;
+        }
+        
+        target_tower.merge_with(&source_tower);
+        drop(target_tower);
+        let _ = self.world.despawn(source);
+        
+        // The merged towers can't be split again, so earlier actions on them
+        // are no longer undoable
+        self.build_history.forget(source);
+        self.build_history.forget(target);
+        true
+    }
+
+    pub fn activate_tower_ability(&mut self, entity: Entity) -> bool {
+        match self.world.get::<&mut Tower>(entity) {
+            Ok(mut tower) => tower.activate_ability(),
+            Err(_) => false,
+        }
+    }
+
+    pub fn set_tower_force_target(&mut self, entity: Entity, point: Option<(f32, f32)>) -> bool {
+        match self.world.get::<&mut Tower>(entity) {
+            Ok(mut tower) => tower.set_force_target(point),
+            Err(_) => false,
+        }
+    }
+
+    pub fn toggle_tower_power(&mut self, entity: Entity) -> bool {
+        match self.world.get::<&mut Tower>(entity) {
+            Ok(mut tower) => {
+                tower.toggle_power();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    // Buy a powerup and start it straight away
+    pub fn activate_powerup(&mut self, powerup_type: PowerupType) -> bool {
+        let mut powerup = Powerup::new(powerup_type);
+        if self.player.get_gold() < powerup.get_cost() {
+            return false;
+        }
+        
+        self.player.spend_gold(powerup.get_cost());
+        powerup.activate();
+        self.powerups.push(powerup);
+        true
+    }
+    
+    pub fn is_powerup_active(&self, powerup_type: PowerupType) -> bool {
+        self.powerups.iter().any(|powerup| powerup.get_type() == powerup_type && powerup.is_active())
+    }
+    
+    pub fn toggle_power_grid(&mut self) {
+        self.power_grid_enabled = !self.power_grid_enabled;
+    }
+
+    pub fn is_power_grid_enabled(&self) -> bool {
+        self.power_grid_enabled
+    }
+
+    pub fn toggle_overkill_prevention(&mut self) {
+        self.overkill_prevention = !self.overkill_prevention;
+        // Start a fresh comparison with the new setting
+        self.damage_fired = 0;
+        self.damage_wasted = 0;
+    }
+
+    pub fn is_overkill_prevention_enabled(&self) -> bool {
+        self.overkill_prevention
+    }
+
+    // Share of fired damage that was wasted on misses and overkill
+    pub fn get_wasted_damage_ratio(&self) -> f32 {
+        if self.damage_fired == 0 {
+            0.0
+        } else {
+            self.damage_wasted as f32 / self.damage_fired as f32
+        }
+    }
+
+    pub fn toggle_route_debug(&mut self) {
+        self.route_debug = !self.route_debug;
+    }
+
+    pub fn is_route_debug_enabled(&self) -> bool {
+        self.route_debug
+    }
+
+    pub fn toggle_merge_mode(&mut self) {
+        self.merge_enabled = !self.merge_enabled;
+    }
+
+    pub fn is_merge_enabled(&self) -> bool {
+        self.merge_enabled
+    }
+
+    // Take back the last placement, upgrade or sale of this build phase at full value
+    pub fn undo(&mut self) -> bool {
+        // Actions on a tower that no longer exists can't be taken back; drop them
+        let action = loop {
+            let Some(action) = self.build_history.pop_undo() else {
+                return false;
+            };
+            if !self.is_stale(&action) {
+                break action;
+            }
+        };
+        
+        match self.revert(action) {
+            Ok(reverted) => {
+                self.build_history.push_undone(reverted);
+                true
+            }
+            Err(action) => {
+                self.build_history.push_redone(action);
+                false
+            }
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let action = loop {
+            let Some(action) = self.build_history.pop_redo() else {
+                return false;
+            };
+            if !self.is_stale(&action) {
+                break action;
+            }
+        };
+        
+        match self.revert(action) {
+            Ok(reverted) => {
+                self.build_history.push_redone(reverted);
+                true
+            }
+            Err(action) => {
+                self.build_history.push_undone(action);
+                false
+            }
+        }
+    }
+
+    fn is_stale(&self, action: &BuildAction) -> bool {
+        action.target().is_some_and(|entity| self.get_tower(entity).is_none())
+    }
+
+    // Apply the opposite of an action. Placing and selling are each other's
+    // inverse and upgrades swap stats with their snapshot, so the same code serves
+    // both undo and redo. Gives the action back untouched if it can't be paid for.
+    fn revert(&mut self, action: BuildAction) -> Result<BuildAction, BuildAction> {
+        match action {
+            BuildAction::Place { entity, cost } => {
+                let Some(tower) = self.get_tower(entity).map(|tower| Box::new(Tower::clone(&tower))) else {
+                    return Err(action);
+                };
+                let _ = self.world.despawn(entity);
+                self.player.add_gold(cost);
+                Ok(BuildAction::Sell { entity, tower, refund: cost })
+            }
+            BuildAction::Sell { entity, tower, refund } => {
+                if self.player.get_gold() < refund {
+                    return Err(BuildAction::Sell { entity, tower, refund });
+                }
+                let new_entity = self.world.spawn((*tower,));
+                self.build_history.remap(entity, new_entity);
+                self.player.spend_gold(refund);
+                Ok(BuildAction::Place { entity: new_entity, cost: refund })
+            }
+            BuildAction::Upgrade { entity, mut snapshot, cost } => {
+                // Undoing refunds the upgrade, redoing pays for it again
+                let upgrading = snapshot.get_level() > self.get_tower(entity).map_or(0, |tower| tower.get_level());
+                if upgrading && self.player.get_gold() < cost {
+                    return Err(BuildAction::Upgrade { entity, snapshot, cost });
+                }
+                let Ok(mut tower) = self.world.get::<&mut Tower>(entity) else {
+                    return Err(BuildAction::Upgrade { entity, snapshot, cost });
+                };
+                tower.swap_upgrade_stats(&mut snapshot);
+                drop(tower);
+                
+                if upgrading {
+                    self.player.spend_gold(cost);
+                } else {
+                    self.player.add_gold(cost);
+                }
+                Ok(BuildAction::Upgrade { entity, snapshot, cost })
+            }
+        }
+    }
+
+    pub fn get_undo_count(&self) -> usize {
+        self.build_history.undo_count()
+    }
+
+    pub fn get_redo_count(&self) -> usize {
+        self.build_history.redo_count()
+    }
 }
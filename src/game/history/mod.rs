@@ -0,0 +1,104 @@
+use hecs::Entity;
+
+use crate::game::tower::Tower;
+
+// A build-phase change the player can take back for a full refund
+pub enum BuildAction {
+    Place { entity: Entity, cost: u32 },
+    Upgrade { entity: Entity, snapshot: Box<Tower>, cost: u32 }, // snapshot is the other side of the upgrade
+    Sell { entity: Entity, tower: Box<Tower>, refund: u32 },
+}
+
+impl BuildAction {
+    // The placed tower the action needs in order to be reverted. A sale
+    // respawns its tower, so it doesn't need one.
+    pub fn target(&self) -> Option<Entity> {
+        match self {
+            BuildAction::Place { entity, .. } | BuildAction::Upgrade { entity, .. } => Some(*entity),
+            BuildAction::Sell { .. } => None,
+        }
+    }
+
+    fn entity(&self) -> Entity {
+        match self {
+            BuildAction::Place { entity, .. } => *entity,
+            BuildAction::Upgrade { entity, .. } => *entity,
+            BuildAction::Sell { entity, .. } => *entity,
+        }
+    }
+
+    fn entity_mut(&mut self) -> &mut Entity {
+        match self {
+            BuildAction::Place { entity, .. } => entity,
+            BuildAction::Upgrade { entity, .. } => entity,
+            BuildAction::Sell { entity, .. } => entity,
+        }
+    }
+}
+
+// Undo/redo stacks for the current build phase. Both are cleared when the
+// next wave starts, which locks in everything built so far.
+pub struct BuildHistory {
+    undo_stack: Vec<BuildAction>,
+    redo_stack: Vec<BuildAction>,
+}
+
+impl BuildHistory {
+    pub fn new() -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+
+    // Record a fresh action; anything that could be redone is now stale
+    pub fn record(&mut self, action: BuildAction) {
+        self.undo_stack.push(action);
+        self.redo_stack.clear();
+    }
+
+    pub fn pop_undo(&mut self) -> Option<BuildAction> {
+        self.undo_stack.pop()
+    }
+
+    pub fn pop_redo(&mut self) -> Option<BuildAction> {
+        self.redo_stack.pop()
+    }
+
+    pub fn push_undone(&mut self, action: BuildAction) {
+        self.redo_stack.push(action);
+    }
+
+    pub fn push_redone(&mut self, action: BuildAction) {
+        self.undo_stack.push(action);
+    }
+
+    // A tower that was despawned and spawned again gets a new entity id
+    pub fn remap(&mut self, old: Entity, new: Entity) {
+        for action in self.undo_stack.iter_mut().chain(self.redo_stack.iter_mut()) {
+            let entity = action.entity_mut();
+            if *entity == old {
+                *entity = new;
+            }
+        }
+    }
+
+    // Drop every action on a tower that can't be brought back as it was
+    pub fn forget(&mut self, entity: Entity) {
+        self.undo_stack.retain(|action| action.entity() != entity);
+        self.redo_stack.retain(|action| action.entity() != entity);
+    }
+
+    pub fn lock(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    pub fn undo_count(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    pub fn redo_count(&self) -> usize {
+        self.redo_stack.len()
+    }
+}
@@ -18,6 +18,21 @@ impl Map1 {
                 (600.0, 150.0),
                 (800.0, 150.0),
             ],
+            branches: vec![
+                // Cuts across the top instead of looping down
+                vec![
+                    (0.0, 300.0),
+                    (150.0, 300.0),
+                    (150.0, 150.0),
+                    (300.0, 150.0),
+                    (450.0, 150.0),
+                    (450.0, 300.0),
+                    (600.0, 300.0),
+                    (600.0, 150.0),
+                    (800.0, 150.0),
+                ],
+            ],
+            air_route: None, // Flyers head straight for the exit
             buildable_areas: vec![
                 (50.0, 350.0, 100.0, 100.0),
                 (200.0, 200.0, 80.0, 80.0),
@@ -25,6 +40,7 @@ impl Map1 {
                 (500.0, 350.0, 80.0, 80.0),
                 (650.0, 200.0, 80.0, 80.0),
             ],
+            wave_script: include_str!("waves/beginner.waves"),
         }
     }
 }
@@ -18,6 +18,26 @@ impl Map2 {
                 (700.0, 200.0),
                 (800.0, 200.0),
             ],
+            branches: vec![
+                // Short way round along the top edge
+                vec![
+                    (0.0, 200.0),
+                    (100.0, 200.0),
+                    (100.0, 400.0),
+                    (300.0, 400.0),
+                    (300.0, 100.0),
+                    (500.0, 100.0),
+                    (700.0, 100.0),
+                    (700.0, 200.0),
+                    (800.0, 200.0),
+                ],
+            ],
+            air_route: Some(vec![
+                (0.0, 200.0),
+                (250.0, 320.0),
+                (550.0, 320.0),
+                (800.0, 200.0),
+            ]),
             buildable_areas: vec![
                 (150.0, 250.0, 100.0, 100.0),
                 (400.0, 200.0, 80.0, 80.0),
@@ -25,6 +45,7 @@ impl Map2 {
                 (600.0, 300.0, 80.0, 80.0),
                 (600.0, 150.0, 80.0, 80.0),
             ],
+            wave_script: include_str!("waves/advanced.waves"),
         }
     }
 }
@@ -8,6 +8,8 @@ use glam::Vec2;
 pub use map1::Map1;
 pub use map2::Map2;
 
+pub const PATH_WIDTH: f32 = 20.0;
+
 #[derive(Debug, Clone, Copy)]
 pub enum MapType {
     Beginner,
@@ -17,15 +19,25 @@ pub enum MapType {
 pub struct Map {
     map_type: MapType,
     path: Vec<(f32, f32)>,
+    branches: Vec<Vec<(f32, f32)>>,      // other ground routes to the exit, starting along `path`
+    air_route: Option<Vec<(f32, f32)>>, // route for flyers, None flies straight to the exit
     buildable_areas: Vec<(f32, f32, f32, f32)>, // x, y, width, height
+    wave_script: &'static str, // contents of the map's wave file
 }
 
 impl Map {
     pub fn new(map_type: MapType) -> Self {
-        match map_type {
+        let mut map = match map_type {
             MapType::Beginner => Map1::create(),
             MapType::Advanced => Map2::create(),
+        };
+        
+        if map.air_route.is_none() {
+            let start = map.path[0];
+            let end = *map.path.last().unwrap_or(&start);
+            map.air_route = Some(vec![start, end]);
         }
+        map
     }
 
     pub fn draw(&self, ctx: &mut ggez::Context, canvas: &mut ggez::graphics::Canvas) -> GameResult {
@@ -44,22 +56,28 @@ impl Map {
         
         canvas.draw(&bg_rect, DrawParam::default());
         
-        // Draw path
-        for i in 0..self.path.len() - 1 {
-            let start = self.path[i];
-            let end = self.path[i + 1];
-            
-            let line = Mesh::new_line(
-                ctx,
-                &[
-                    Vec2::new(start.0, start.1),
-                    Vec2::new(end.0, end.1),
-                ],
-                20.0, // Path width
-                [0.8, 0.7, 0.5, 1.0].into(),
-            )?;
-            
-            canvas.draw(&line, DrawParam::default());
+        // Draw path and its branches
+        for route in std::iter::once(&self.path).chain(&self.branches) {
+            for segment in route.windows(2) {
+                let line = Mesh::new_line(
+                    ctx,
+                    &[
+                        Vec2::new(segment[0].0, segment[0].1),
+                        Vec2::new(segment[1].0, segment[1].1),
+                    ],
+                    PATH_WIDTH,
+                    [0.8, 0.7, 0.5, 1.0].into(),
+                )?;
+                
+                canvas.draw(&line, DrawParam::default());
+            }
+        }
+        
+        // Draw the air route flyers follow
+        if let Some(air_route) = &self.air_route {
+            let points: Vec<Vec2> = air_route.iter().map(|point| Vec2::new(point.0, point.1)).collect();
+            let air_line = Mesh::new_line(ctx, &points, 2.0, [0.7, 0.9, 1.0, 0.3].into())?;
+            canvas.draw(&air_line, DrawParam::default());
         }
         
         // Draw buildable areas with light highlighting
@@ -89,14 +107,102 @@ impl Map {
         false
     }
     
-    pub fn get_path(&self) -> &[(f32, f32)] {
-        &self.path
+    // Waypoints an enemy follows: route 0 is the main path, later routes are
+    // the branches, and flyers always take the air route
+    pub fn get_route(&self, route: usize, flying: bool) -> &[(f32, f32)] {
+        match &self.air_route {
+            Some(air_route) if flying => air_route,
+            _ => route.checked_sub(1).and_then(|branch| self.branches.get(branch)).unwrap_or(&self.path),
+        }
+    }
+    
+    // Ground routes to the exit, including the main path
+    pub fn route_count(&self) -> usize {
+        1 + self.branches.len()
+    }
+    
+    pub fn route_length(&self, route: usize) -> f32 {
+        polyline_length(self.get_route(route, false))
+    }
+    
+    // How far two ground routes run together from the start before they split
+    pub fn shared_length(&self, a: usize, b: usize) -> f32 {
+        let (a, b) = (self.get_route(a, false), self.get_route(b, false));
+        let shared = a.iter().zip(b).take_while(|(p, q)| p == q).count();
+        polyline_length(&a[..shared])
+    }
+    
+    // Point and heading `distance` pixels along a route, None once past its end
+    pub fn route_point(&self, route: usize, flying: bool, distance: f32) -> Option<((f32, f32), (f32, f32))> {
+        let mut remaining = distance.max(0.0);
+        for segment in self.get_route(route, flying).windows(2) {
+            let start = Vec2::new(segment[0].0, segment[0].1);
+            let end = Vec2::new(segment[1].0, segment[1].1);
+            let length = start.distance(end);
+            if remaining <= length {
+                let heading = (end - start).normalize_or_zero();
+                let point = start + heading * remaining;
+                return Some(((point.x, point.y), (heading.x, heading.y)));
+            }
+            remaining -= length;
+        }
+        
+        None
+    }
+    
+    // How far along a route its closest point to the given position lies
+    pub fn route_progress(&self, route: usize, flying: bool, x: f32, y: f32) -> f32 {
+        let point = Vec2::new(x, y);
+        let mut travelled = 0.0;
+        let mut best = (f32::MAX, 0.0);
+        
+        for segment in self.get_route(route, flying).windows(2) {
+            let start = Vec2::new(segment[0].0, segment[0].1);
+            let end = Vec2::new(segment[1].0, segment[1].1);
+            let along = end - start;
+            let t = ((point - start).dot(along) / along.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+            let dist = (start + along * t).distance_squared(point);
+            
+            if dist < best.0 {
+                best = (dist, travelled + along.length() * t);
+            }
+            travelled += along.length();
+        }
+        
+        best.1
     }
     
     pub fn get_path_start(&self) -> (f32, f32) {
         self.path[0]
     }
     
+    pub fn get_wave_script(&self) -> &'static str {
+        self.wave_script
+    }
+    
+    // Closest point on the path or any of its branches to the given position
+    pub fn nearest_path_point(&self, x: f32, y: f32) -> (f32, f32) {
+        let point = Vec2::new(x, y);
+        let mut nearest = self.path[0];
+        let mut nearest_dist = f32::MAX;
+        
+        for segment in std::iter::once(&self.path).chain(&self.branches).flat_map(|route| route.windows(2)) {
+            let start = Vec2::new(segment[0].0, segment[0].1);
+            let end = Vec2::new(segment[1].0, segment[1].1);
+            let along = end - start;
+            let t = ((point - start).dot(along) / along.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+            let candidate = start + along * t;
+            let dist = candidate.distance_squared(point);
+            
+            if dist < nearest_dist {
+                nearest_dist = dist;
+                nearest = (candidate.x, candidate.y);
+            }
+        }
+        
+        nearest
+    }
+    
     // pub fn get_path_end(&self) -> (f32, f32) {
     //     // Return the last point in the path
     //     *self.path.last().unwrap_or(&(0.0, 0.0))
@@ -106,3 +212,10 @@ impl Map {
     //     self.map_type
     // }
 }
+
+// Total length of a polyline
+fn polyline_length(points: &[(f32, f32)]) -> f32 {
+    points.windows(2)
+        .map(|segment| Vec2::new(segment[0].0, segment[0].1).distance(Vec2::new(segment[1].0, segment[1].1)))
+        .sum()
+}
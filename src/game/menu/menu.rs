@@ -1,6 +1,5 @@
-use crate::game::GameState; // Assuming this exists
-use ggez::event::KeyCode;
-use ggez::graphics::{self, Color, DrawParam, Text};
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::input::keyboard::KeyCode;
 use ggez::{Context, GameResult};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -14,6 +13,14 @@ pub enum MenuOption {
     ExitGame,
 }
 
+// What the game loop should do after a menu key press
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MenuAction {
+    Resume,
+    QuitToMainMenu,
+    ExitGame,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DifficultyLevel {
     Easy,
@@ -48,6 +55,12 @@ pub struct PauseMenu {
     music_volume: f32,
 }
 
+impl Default for PauseMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PauseMenu {
     pub fn new() -> Self {
         // Default values
@@ -76,7 +89,7 @@ impl PauseMenu {
         }
     }
     
-    pub fn handle_key_press(&mut self, key: KeyCode) -> Option<GameState> {
+    pub fn handle_key_press(&mut self, key: KeyCode) -> Option<MenuAction> {
         match key {
             KeyCode::Up => {
                 if self.selected_index > 0 {
@@ -106,7 +119,7 @@ impl PauseMenu {
                 self.select_option()
             }
             KeyCode::Escape => {
-                Some(GameState::Playing)
+                Some(MenuAction::Resume)
             }
             _ => None,
         }
@@ -115,15 +128,15 @@ impl PauseMenu {
     fn adjust_option(&mut self, direction: i32) {
         match &self.options[self.selected_index] {
             MenuOption::GameSpeed(_) => {
-                self.game_speed = (self.game_speed + 0.1 * direction as f32).max(0.5).min(2.0);
+                self.game_speed = (self.game_speed + 0.1 * direction as f32).clamp(0.5, 2.0);
                 self.options[self.selected_index] = MenuOption::GameSpeed(self.game_speed);
             }
             MenuOption::SoundVolume(_) => {
-                self.sound_volume = (self.sound_volume + 0.05 * direction as f32).max(0.0).min(1.0);
+                self.sound_volume = (self.sound_volume + 0.05 * direction as f32).clamp(0.0, 1.0);
                 self.options[self.selected_index] = MenuOption::SoundVolume(self.sound_volume);
             }
             MenuOption::MusicVolume(_) => {
-                self.music_volume = (self.music_volume + 0.05 * direction as f32).max(0.0).min(1.0);
+                self.music_volume = (self.music_volume + 0.05 * direction as f32).clamp(0.0, 1.0);
                 self.options[self.selected_index] = MenuOption::MusicVolume(self.music_volume);
             }
             MenuOption::Difficulty(_) => {
@@ -134,16 +147,25 @@ impl PauseMenu {
         }
     }
     
-    fn select_option(&self) -> Option<GameState> {
+    fn select_option(&self) -> Option<MenuAction> {
         match &self.options[self.selected_index] {
-            MenuOption::Resume => Some(GameState::Playing),
-            MenuOption::QuitToMainMenu => Some(GameState::MainMenu),
-            MenuOption::ExitGame => Some(GameState::Exit),
+            MenuOption::Resume => Some(MenuAction::Resume),
+            MenuOption::QuitToMainMenu => Some(MenuAction::QuitToMainMenu),
+            MenuOption::ExitGame => Some(MenuAction::ExitGame),
             _ => None, // For options that are adjusted with left/right
         }
     }
     
-    pub fn draw(&self, ctx: &mut Context) -> GameResult<()> {
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult<()> {
+        // Dim the field behind the menu
+        let backdrop = Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(0.0, 0.0, 1024.0, 768.0),
+            [0.0, 0.0, 0.0, 0.6].into(),
+        )?;
+        canvas.draw(&backdrop, DrawParam::default());
+        
         let mut y = 150.0;
         let line_height = 40.0;
         
@@ -165,14 +187,13 @@ impl PauseMenu {
                 Color::WHITE
             };
             
-            graphics::draw(
-                ctx,
+            canvas.draw(
                 &text,
                 DrawParam::default()
-                    .dest([400.0, y])
+                    .dest([512.0, y])
                     .color(color)
                     .offset([0.5, 0.0]),
-            )?;
+            );
             
             y += line_height;
         }
@@ -1,3 +1,3 @@
-pub use menu::PauseMenu;
+pub use menu::{DifficultyLevel, MenuAction, PauseMenu};
 
-mod menu;
\ No newline at end of file
+mod menu;
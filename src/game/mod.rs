@@ -1,11 +1,17 @@
+pub mod economy;
 pub mod enemy;
+pub mod history;
 pub mod map;
 pub mod player;
+pub mod power;
 pub mod powerup;
 pub mod tower;
+pub mod wave;
 pub mod menu;
 
 pub use game::Game;
 pub use game::GameState; // Export GameState
+pub use game::BossStatus;
+pub use game::WavePreview;
 
 mod game;
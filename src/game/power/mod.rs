@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use ggez::GameResult;
+use ggez::graphics::{Canvas, DrawParam, Mesh};
+use glam::Vec2;
+use hecs::Entity;
+
+// A tower as seen by the power grid
+pub struct PowerNode {
+    pub entity: Entity,
+    pub position: (f32, f32),
+    pub demand: f32,  // power drawn by a switched-on tower
+    pub output: f32,  // power supplied by a generator
+    pub radius: f32,  // how far a generator reaches
+}
+
+struct PowerLink {
+    generator: (f32, f32),
+    consumer: (f32, f32),
+    overloaded: bool,
+}
+
+// Which generator feeds each tower and how much of its demand is met
+pub struct PowerGrid {
+    ratios: HashMap<Entity, f32>,
+    links: Vec<PowerLink>,
+}
+
+impl PowerGrid {
+    pub fn new() -> Self {
+        Self {
+            ratios: HashMap::new(),
+            links: Vec::new(),
+        }
+    }
+
+    // Each tower hooks up to the nearest generator that reaches it. A generator
+    // with more demand than output shares what it has, so every tower on it
+    // fires slower; towers outside every grid get no power at all.
+    pub fn compute(nodes: &[PowerNode]) -> Self {
+        let generators: Vec<&PowerNode> = nodes.iter().filter(|node| node.output > 0.0).collect();
+        let mut loads = vec![0.0; generators.len()];
+        let mut connections = Vec::new();
+        let mut grid = Self::new();
+        
+        for consumer in nodes.iter().filter(|node| node.output <= 0.0 && node.demand > 0.0) {
+            let nearest = generators.iter()
+                .enumerate()
+                .map(|(i, generator)| (i, distance_squared(generator.position, consumer.position)))
+                .filter(|&(i, dist)| dist <= generators[i].radius * generators[i].radius)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            
+            match nearest {
+                Some((i, _)) => {
+                    loads[i] += consumer.demand;
+                    connections.push((consumer, i));
+                }
+                None => {
+                    grid.ratios.insert(consumer.entity, 0.0);
+                }
+            }
+        }
+        
+        for (consumer, i) in connections {
+            let generator = generators[i];
+            let ratio = (generator.output / loads[i]).min(1.0);
+            grid.ratios.insert(consumer.entity, ratio);
+            grid.links.push(PowerLink {
+                generator: generator.position,
+                consumer: consumer.position,
+                overloaded: ratio < 1.0,
+            });
+        }
+        
+        grid
+    }
+
+    // Share of its demand a tower gets; towers not drawing power count as unpowered
+    pub fn ratio_for(&self, entity: Entity) -> f32 {
+        self.ratios.get(&entity).copied().unwrap_or(0.0)
+    }
+
+    pub fn draw(&self, ctx: &mut ggez::Context, canvas: &mut Canvas) -> GameResult {
+        for link in &self.links {
+            let line = Mesh::new_line(
+                ctx,
+                &[
+                    Vec2::new(link.generator.0, link.generator.1),
+                    Vec2::new(link.consumer.0, link.consumer.1),
+                ],
+                1.5,
+                if link.overloaded {
+                    [1.0, 0.3, 0.1, 0.6].into()
+                } else {
+                    [1.0, 0.9, 0.2, 0.6].into()
+                },
+            )?;
+            canvas.draw(&line, DrawParam::default());
+        }
+        
+        Ok(())
+    }
+}
+
+fn distance_squared(a: (f32, f32), b: (f32, f32)) -> f32 {
+    let dx = a.0 - b.0;
+    let dy = a.1 - b.1;
+    dx * dx + dy * dy
+}
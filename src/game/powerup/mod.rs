@@ -1,13 +1,16 @@
 mod powerup1;
 mod powerup2;
+mod powerup3;
 
 pub use powerup1::Powerup1;
 pub use powerup2::Powerup2;
+pub use powerup3::Powerup3;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PowerupType {
     DamageBoost,
     GoldBoost,
+    Reveal,
 }
 
 pub struct Powerup {
@@ -22,6 +25,7 @@ impl Powerup {
         match powerup_type {
             PowerupType::DamageBoost => Powerup1::create(),
             PowerupType::GoldBoost => Powerup2::create(),
+            PowerupType::Reveal => Powerup3::create(),
         }
     }
 
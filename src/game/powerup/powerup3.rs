@@ -0,0 +1,14 @@
+use super::{Powerup, PowerupType};
+
+pub struct Powerup3;
+
+impl Powerup3 {
+    pub fn create() -> Powerup {
+        Powerup {
+            powerup_type: PowerupType::Reveal, // Every stealth enemy on the map can be targeted
+            duration: 15.0, // 15 seconds
+            active: false,
+            cost: 80,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use super::TowerType;
+
+// Active abilities the player triggers from the selected-tower panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ability {
+    Overcharge,   // doubles fire rate for a few seconds
+    PiercingShot, // the next shot hits every enemy along its line
+}
+
+impl Ability {
+    pub fn for_tower(tower_type: TowerType) -> Option<Self> {
+        match tower_type {
+            TowerType::Basic => Some(Ability::Overcharge),
+            TowerType::Advanced => Some(Ability::PiercingShot),
+            TowerType::Generator => None,
+            TowerType::Mortar => None,
+            TowerType::Detector => None,
+            TowerType::Gravity => None,
+            TowerType::Flak => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ability::Overcharge => "Overcharge",
+            Ability::PiercingShot => "Piercing Shot",
+        }
+    }
+
+    pub fn cooldown(&self) -> f32 {
+        match self {
+            Ability::Overcharge => 30.0,
+            Ability::PiercingShot => 20.0,
+        }
+    }
+
+    // How long the ability stays active; piercing lasts until the next shot
+    pub fn duration(&self) -> f32 {
+        match self {
+            Ability::Overcharge => 5.0,
+            Ability::PiercingShot => f32::INFINITY,
+        }
+    }
+}
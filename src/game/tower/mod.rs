@@ -1,29 +1,198 @@
+mod ability;
 mod tower1;
 mod tower2;
+mod tower3;
+mod tower4;
+mod tower5;
+mod tower6;
+mod tower7;
 
-use ggez::graphics::{DrawMode, DrawParam, Mesh};
+use ggez::graphics::{DrawMode, DrawParam, Mesh, Rect};
 use ggez::GameResult;
 use glam::Vec2;
+use hecs::Entity;
+use std::f32::consts::{PI, TAU};
 
+pub use ability::Ability;
 pub use tower1::Tower1;
 pub use tower2::Tower2;
+pub use tower3::Tower3;
+pub use tower4::Tower4;
+pub use tower5::Tower5;
+pub use tower6::Tower6;
+pub use tower7::Tower7;
 
-#[derive(Debug, Clone, Copy)]
+// XP needed to reach each veterancy rank
+const RANK_THRESHOLDS: [u32; 4] = [0, 150, 400, 900];
+const XP_PER_KILL: u32 = 25;
+const RANK_DAMAGE_BONUS: f32 = 0.1;    // +10% damage per rank
+const RANK_FIRE_RATE_BONUS: f32 = 0.05; // +5% fire rate per rank
+const AIM_TOLERANCE: f32 = 0.05; // radians off target that still counts as aimed
+const DETECTION_LEVEL: u32 = 3;  // upgraded this far, towers spot stealth enemies in their own range
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TowerType {
     Basic,
     Advanced,
+    Generator,
+    Mortar,
+    Detector,
+    Gravity,
+    Flak,
+}
+
+// Which kinds of enemies a tower's shots can hit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TargetLayers {
+    Ground,
+    Air,
+    Both,
+}
+
+impl TargetLayers {
+    pub fn can_hit(&self, flying: bool) -> bool {
+        match self {
+            TargetLayers::Ground => !flying,
+            TargetLayers::Air => flying,
+            TargetLayers::Both => true,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TargetLayers::Ground => "Ground",
+            TargetLayers::Air => "Air",
+            TargetLayers::Both => "Ground + Air",
+        }
+    }
+}
+
+// How a tower's damage interacts with enemy shields
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DamageType {
+    Kinetic,
+    Energy,     // tears through shields
+    Concussive, // shockwaves pass straight through shields
+}
+
+impl DamageType {
+    // How many shield points each point of damage removes
+    pub fn shield_multiplier(&self) -> f32 {
+        match self {
+            DamageType::Kinetic => 1.0,
+            DamageType::Energy => 2.0,
+            DamageType::Concussive => 1.0,
+        }
+    }
+    
+    pub fn bypasses_shields(&self) -> bool {
+        matches!(self, DamageType::Concussive)
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageType::Kinetic => "Kinetic",
+            DamageType::Energy => "Energy",
+            DamageType::Concussive => "Concussive",
+        }
+    }
+}
+
+// What a tower knows about an enemy it could shoot at
+#[derive(Debug, Clone, Copy)]
+pub struct TargetInfo {
+    pub entity: Entity,
+    pub position: (f32, f32),
+    pub velocity: (f32, f32),
+    pub health: u32,
+    pub shield: u32,
+    pub pending_damage: u32, // damage from shots already on their way
+    pub flying: bool,
+    pub hidden: bool, // stealthy and outside every detection source
+    pub untargetable: bool, // burrowed or otherwise out of reach of every tower
 }
 
+impl TargetInfo {
+    // Shots already heading its way will break its shield and kill it
+    pub fn is_doomed(&self) -> bool {
+        self.pending_damage >= self.health + self.shield
+    }
+}
+
+// Shells that land on a point on the ground after a flight time
+#[derive(Debug, Clone, Copy)]
+pub struct Artillery {
+    pub splash_radius: f32,
+    pub shell_speed: f32, // pixels per second
+}
+
+// Moves enemies along their route when a shot hits
+#[derive(Debug, Clone, Copy)]
+pub enum PathEffect {
+    Knockback(f32),                             // pixels back along the route
+    Pull { toward: (f32, f32), distance: f32 }, // pixels towards a point's spot on the route
+}
+
+// A shot fired by a tower this tick
+pub struct Shot {
+    pub target: (f32, f32),
+    pub target_entity: Option<Entity>, // None when shelling a forced ground point
+    pub damage: u32,
+    pub layers: TargetLayers,
+    pub damage_type: DamageType,
+    pub piercing: bool, // hits every enemy along the line out to the tower's range
+    pub path_effect: Option<PathEffect>,
+    pub shell: Option<Shell>, // set for artillery, which hits the ground later
+}
+
+// An artillery shell in flight
+#[derive(Debug, Clone, Copy)]
+pub struct Shell {
+    pub splash_radius: f32,
+    pub flight_time: f32,
+}
+
+// How two towers of the same type combine when merged
+#[derive(Debug, Clone, Copy)]
+pub struct MergeRules {
+    pub max_level: u32,  // towers at this level can't be merged further
+    pub stat_boost: f32, // extra multiplier on top of a normal upgrade
+}
+
+#[derive(Clone)]
 pub struct Tower {
     tower_type: TowerType,
     x: f32,
     y: f32,
     range: f32,
+    min_range: f32, // enemies closer than this can't be targeted
     damage: u32,
     fire_rate: f32, // shots per second
     fire_cooldown: f32,
+    facing: f32,       // current barrel angle in radians
+    base_facing: f32,  // centre of the firing arc, set when placed
+    turn_rate: f32,    // radians per second
+    firing_arc: Option<f32>, // half-width of the arc, None turns freely
     level: u32,
+    health: u32,
+    max_health: u32,
     cost: u32,
+    kills: u32,
+    damage_dealt: u32,
+    xp: u32,
+    rank: u32,
+    ability_cooldown: f32, // seconds until the ability can be used again
+    ability_time: f32,     // seconds the ability stays active
+    stun_time: f32,        // seconds until a stunned tower can act again
+    power_draw: f32,       // power needed to fire at full rate
+    power_output: f32,     // power supplied to towers in the grid radius
+    grid_radius: f32,
+    detection_radius: f32, // reveals stealth enemies to every tower
+    powered_on: bool,      // switched off towers don't fire or draw power
+    power_ratio: f32,      // share of the power draw currently supplied
+    artillery: Option<Artillery>,
+    force_target: Option<(f32, f32)>, // ground point the player told the tower to shell
+    path_effect: Option<PathEffect>,  // applied to every enemy the tower's shots hit
 }
 
 impl Tower {
@@ -31,34 +200,190 @@ impl Tower {
         match tower_type {
             TowerType::Basic => Tower1::create(x, y),
             TowerType::Advanced => Tower2::create(x, y),
+            TowerType::Generator => Tower3::create(x, y),
+            TowerType::Mortar => Tower4::create(x, y),
+            TowerType::Detector => Tower5::create(x, y),
+            TowerType::Gravity => Tower6::create(x, y),
+            TowerType::Flak => Tower7::create(x, y),
         }
     }
 
-    pub fn update(&mut self, dt: f32, enemies: &[(f32, f32)]) -> Option<(f32, f32)> {
+    pub fn update(&mut self, dt: f32, enemies: &[TargetInfo]) -> Option<Shot> {
         self.fire_cooldown -= dt;
+        self.ability_cooldown = (self.ability_cooldown - dt).max(0.0);
+        self.ability_time = (self.ability_time - dt).max(0.0);
+        self.stun_time = (self.stun_time - dt).max(0.0);
         
-        // Find closest enemy in range
-        if self.fire_cooldown <= 0.0 {
-            if let Some(target) = self.find_target(enemies) {
-                self.fire_cooldown = 1.0 / self.fire_rate;
-                return Some(target);
+        if self.is_stunned() || self.is_generator() || self.is_detector() || self.power_ratio <= 0.0 {
+            return None;
+        }
+        
+        // Turn towards the forced ground target or the first enemy in range,
+        // and fire once the barrel is on it
+        let (target, target_entity) = match self.force_target.filter(|&point| self.in_range(point)) {
+            Some(point) => (point, None),
+            None => {
+                let enemy = self.find_target(enemies)?;
+                (self.lead_target(enemy), Some(enemy.entity))
             }
+        };
+        let aim_error = self.turn_towards(self.angle_to(target), dt);
+        
+        if self.fire_cooldown <= 0.0 && aim_error <= AIM_TOLERANCE {
+            self.fire_cooldown = 1.0 / self.get_fire_rate();
+            
+            // A charged piercing shot is spent on this shot
+            let piercing = self.is_ability_active(Ability::PiercingShot);
+            if piercing {
+                self.ability_time = 0.0;
+            }
+            
+            let shell = self.artillery.map(|artillery| Shell {
+                splash_radius: artillery.splash_radius,
+                flight_time: self.distance_to(target) / artillery.shell_speed,
+            });
+            
+            return Some(Shot {
+                target,
+                target_entity,
+                damage: self.get_damage(),
+                layers: self.tower_type.target_layers(),
+                damage_type: self.tower_type.damage_type(),
+                piercing,
+                path_effect: self.path_effect,
+                shell,
+            });
         }
         
         None
     }
 
-    fn find_target(&self, enemies: &[(f32, f32)]) -> Option<(f32, f32)> {
+    pub fn get_ability(&self) -> Option<Ability> {
+        Ability::for_tower(self.tower_type)
+    }
+
+    pub fn activate_ability(&mut self) -> bool {
+        let Some(ability) = self.get_ability() else {
+            return false;
+        };
+        if self.ability_cooldown > 0.0 {
+            return false;
+        }
+        
+        self.ability_cooldown = ability.cooldown();
+        self.ability_time = ability.duration();
+        true
+    }
+
+    fn is_ability_active(&self, ability: Ability) -> bool {
+        self.ability_time > 0.0 && self.get_ability() == Some(ability)
+    }
+
+    pub fn stun(&mut self, duration: f32) {
+        self.stun_time = self.stun_time.max(duration);
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.stun_time > 0.0
+    }
+
+    pub fn get_ability_cooldown(&self) -> f32 {
+        self.ability_cooldown
+    }
+
+    fn find_target(&self, enemies: &[TargetInfo]) -> Option<TargetInfo> {
         enemies.iter()
-            .filter(|(ex, ey)| {
-                let dx = ex - self.x;
-                let dy = ey - self.y;
-                dx*dx + dy*dy <= self.range*self.range
-            })
+            .filter(|enemy| !enemy.is_doomed() && !enemy.hidden && !enemy.untargetable)
+            .filter(|enemy| self.tower_type.target_layers().can_hit(enemy.flying))
+            .filter(|enemy| self.in_range(enemy.position))
+            .filter(|enemy| self.in_firing_arc(self.angle_to(enemy.position)))
             .copied()
             .next()
     }
 
+    // Inside the outer range and outside the inner dead zone
+    fn in_range(&self, point: (f32, f32)) -> bool {
+        let dist = self.distance_to(point);
+        dist >= self.min_range && dist <= self.range
+    }
+
+    // Aim where a slow shell will meet the enemy rather than where it is now
+    fn lead_target(&self, target: TargetInfo) -> (f32, f32) {
+        let Some(artillery) = self.artillery else {
+            return target.position;
+        };
+        
+        let mut aim = target.position;
+        for _ in 0..2 {
+            let flight_time = self.distance_to(aim) / artillery.shell_speed;
+            aim = (
+                target.position.0 + target.velocity.0 * flight_time,
+                target.position.1 + target.velocity.1 * flight_time,
+            );
+        }
+        
+        // Shoot where the enemy is now if the shell couldn't land on the prediction
+        if self.in_range(aim) {
+            aim
+        } else {
+            target.position
+        }
+    }
+
+    fn distance_to(&self, point: (f32, f32)) -> f32 {
+        let dx = point.0 - self.x;
+        let dy = point.1 - self.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    pub fn has_ground_targeting(&self) -> bool {
+        self.artillery.is_some()
+    }
+
+    // Fix the ground point an artillery tower shells, or clear it with None
+    pub fn set_force_target(&mut self, point: Option<(f32, f32)>) -> bool {
+        if self.artillery.is_none() || point.is_some_and(|point| !self.in_range(point)) {
+            return false;
+        }
+        self.force_target = point;
+        true
+    }
+
+    pub fn get_force_target(&self) -> Option<(f32, f32)> {
+        self.force_target
+    }
+
+    // Rotate the barrel by at most turn_rate * dt, returns the remaining error
+    fn turn_towards(&mut self, angle: f32, dt: f32) -> f32 {
+        let diff = angle_diff(angle, self.facing);
+        let max_step = self.turn_rate * dt;
+        
+        if diff.abs() <= max_step {
+            self.facing = angle;
+            0.0
+        } else {
+            self.facing = (self.facing + max_step * diff.signum()).rem_euclid(TAU);
+            diff.abs() - max_step
+        }
+    }
+
+    fn angle_to(&self, target: (f32, f32)) -> f32 {
+        (target.1 - self.y).atan2(target.0 - self.x).rem_euclid(TAU)
+    }
+
+    fn in_firing_arc(&self, angle: f32) -> bool {
+        match self.firing_arc {
+            Some(half_width) => angle_diff(angle, self.base_facing).abs() <= half_width,
+            None => true,
+        }
+    }
+
+    // Point the tower (and the centre of its firing arc) at a position
+    pub fn face_towards(&mut self, point: (f32, f32)) {
+        self.base_facing = self.angle_to(point);
+        self.facing = self.base_facing;
+    }
+
     pub fn draw(&self, ctx: &mut ggez::Context, canvas: &mut ggez::graphics::Canvas) -> GameResult {
         // Draw the tower base
         let circle = Mesh::new_circle(
@@ -70,11 +395,122 @@ impl Tower {
             match self.tower_type {
                 TowerType::Basic => [0.2, 0.6, 0.8, 1.0].into(),
                 TowerType::Advanced => [0.8, 0.2, 0.8, 1.0].into(),
+                TowerType::Generator => [0.9, 0.8, 0.1, 1.0].into(),
+                TowerType::Mortar => [0.5, 0.5, 0.3, 1.0].into(),
+                TowerType::Detector => [0.3, 0.9, 0.9, 1.0].into(),
+                TowerType::Gravity => [0.4, 0.2, 0.6, 1.0].into(),
+                TowerType::Flak => [0.7, 0.3, 0.2, 1.0].into(),
             },
         )?;
         
         canvas.draw(&circle, DrawParam::default());
         
+        // Damaged towers show how much health they have left
+        if self.health < self.max_health {
+            let health_bar = Mesh::new_rectangle(
+                ctx,
+                DrawMode::fill(),
+                Rect::new(self.x - 10.0, self.y + 13.0, 20.0 * self.health as f32 / self.max_health as f32, 3.0),
+                [1.0, 0.3, 0.2, 1.0].into(),
+            )?;
+            canvas.draw(&health_bar, DrawParam::default());
+        }
+        
+        if self.is_generator() || self.is_detector() {
+            return Ok(());
+        }
+        
+        // Mark towers that are switched off, unpowered or on an overloaded grid
+        if self.power_ratio < 1.0 {
+            let power_marker = Mesh::new_circle(
+                ctx,
+                DrawMode::fill(),
+                Vec2::new(self.x + 8.0, self.y + 8.0),
+                3.0,
+                0.1,
+                if self.power_ratio <= 0.0 {
+                    [1.0, 0.1, 0.1, 1.0].into()
+                } else {
+                    [1.0, 0.6, 0.1, 1.0].into()
+                },
+            )?;
+            canvas.draw(&power_marker, DrawParam::default());
+        }
+        
+        // Stunned towers get a white ring until they recover
+        if self.is_stunned() {
+            let stun_ring = Mesh::new_circle(
+                ctx,
+                DrawMode::stroke(2.0),
+                Vec2::new(self.x, self.y),
+                13.0,
+                0.1,
+                [1.0, 1.0, 1.0, 0.8].into(),
+            )?;
+            canvas.draw(&stun_ring, DrawParam::default());
+        }
+        
+        // Draw the limits of the firing arc
+        if let Some(half_width) = self.firing_arc {
+            for edge in [self.base_facing - half_width, self.base_facing + half_width] {
+                let arc_edge = Mesh::new_line(
+                    ctx,
+                    &[
+                        Vec2::new(self.x, self.y),
+                        Vec2::new(self.x + edge.cos() * 25.0, self.y + edge.sin() * 25.0),
+                    ],
+                    1.0,
+                    [1.0, 1.0, 1.0, 0.3].into(),
+                )?;
+                canvas.draw(&arc_edge, DrawParam::default());
+            }
+        }
+        
+        // Draw the barrel so players can see where the tower is aiming
+        let barrel = Mesh::new_line(
+            ctx,
+            &[
+                Vec2::new(self.x, self.y),
+                Vec2::new(self.x + self.facing.cos() * 16.0, self.y + self.facing.sin() * 16.0),
+            ],
+            4.0,
+            [0.15, 0.15, 0.15, 1.0].into(),
+        )?;
+        canvas.draw(&barrel, DrawParam::default());
+        
+        // Draw the ability cooldown as a ring that shrinks as it recharges
+        if let Some(ability) = self.get_ability() {
+            let remaining = self.ability_cooldown / ability.cooldown();
+            if remaining > 0.01 {
+                let segments = (remaining * 32.0).ceil() as usize;
+                let sweep = remaining * TAU;
+                let points: Vec<Vec2> = (0..=segments)
+                    .map(|i| {
+                        let angle = -PI / 2.0 + sweep * i as f32 / segments as f32;
+                        Vec2::new(self.x + angle.cos() * 13.0, self.y + angle.sin() * 13.0)
+                    })
+                    .collect();
+                let timer = Mesh::new_line(ctx, &points, 2.0, [0.9, 0.9, 0.9, 0.8].into())?;
+                canvas.draw(&timer, DrawParam::default());
+            }
+        }
+        
+        // Draw one chevron per veterancy rank above the tower
+        for i in 0..self.rank {
+            let y = self.y - 16.0 - i as f32 * 4.0;
+            let chevron = Mesh::new_line(
+                ctx,
+                &[
+                    Vec2::new(self.x - 5.0, y - 3.0),
+                    Vec2::new(self.x, y),
+                    Vec2::new(self.x + 5.0, y - 3.0),
+                ],
+                1.5,
+                [1.0, 0.85, 0.2, 1.0].into(),
+            )?;
+            canvas.draw(&chevron, DrawParam::default());
+        }
+        
         // Draw range indicator when selected (could be toggled with a flag)
         if self.fire_cooldown < 0.1 {
             // Optional: Draw shooting indicator when tower just fired
@@ -92,15 +528,91 @@ impl Tower {
         Ok(())
     }
 
+    pub fn get_upgrade_cost(&self) -> u32 {
+        self.cost / 2
+    }
+
     pub fn upgrade(&mut self) -> u32 {
         let upgrade_cost = self.cost / 2;
         self.level += 1;
         self.damage += self.damage / 2;
         self.range += 10.0;
         self.fire_rate *= 1.2;  // 20% faster firing
+        self.health += self.max_health / 5;
+        self.max_health += self.max_health / 5;
+        if self.is_generator() {
+            self.power_output *= 1.5;
+            self.grid_radius += 20.0;
+        }
+        if self.is_detector() {
+            self.detection_radius += 30.0;
+        }
         upgrade_cost
     }
 
+    // Trade level and upgrade stats with another copy of this tower, keeping
+    // kills, XP, rank, cooldowns and damage taken. Undo and redo use this to
+    // move a tower between the two sides of an upgrade.
+    pub fn swap_upgrade_stats(&mut self, other: &mut Tower) {
+        let damage_taken = self.max_health.saturating_sub(self.health);
+        std::mem::swap(&mut self.level, &mut other.level);
+        std::mem::swap(&mut self.damage, &mut other.damage);
+        std::mem::swap(&mut self.range, &mut other.range);
+        std::mem::swap(&mut self.fire_rate, &mut other.fire_rate);
+        std::mem::swap(&mut self.max_health, &mut other.max_health);
+        std::mem::swap(&mut self.power_output, &mut other.power_output);
+        std::mem::swap(&mut self.grid_radius, &mut other.grid_radius);
+        std::mem::swap(&mut self.detection_radius, &mut other.detection_radius);
+        self.health = self.max_health.saturating_sub(damage_taken).max(1);
+    }
+
+    // Credit a shot that landed; returns true if the tower ranked up
+    pub fn record_hit(&mut self, damage: u32, killed: bool) -> bool {
+        self.damage_dealt += damage;
+        if killed {
+            self.kills += 1;
+        }
+        self.gain_xp(damage + if killed { XP_PER_KILL } else { 0 })
+    }
+
+    fn gain_xp(&mut self, xp: u32) -> bool {
+        self.xp += xp;
+        
+        let max_rank = RANK_THRESHOLDS.len() as u32 - 1;
+        let mut ranked_up = false;
+        while self.rank < max_rank && self.xp >= RANK_THRESHOLDS[self.rank as usize + 1] {
+            self.rank += 1;
+            ranked_up = true;
+        }
+        ranked_up
+    }
+
+    pub fn can_merge_with(&self, other: &Tower) -> bool {
+        match self.tower_type.merge_rules() {
+            Some(rules) => self.tower_type == other.tower_type &&
+                           self.level == other.level &&
+                           self.level < rules.max_level,
+            None => false,
+        }
+    }
+
+    // Absorb another tower of the same type and level: one level up with
+    // boosted stats, keeping the combined veterancy of both
+    pub fn merge_with(&mut self, other: &Tower) {
+        let Some(rules) = self.tower_type.merge_rules() else {
+            return;
+        };
+        
+        self.upgrade();
+        self.damage = (self.damage as f32 * rules.stat_boost) as u32;
+        self.range *= rules.stat_boost;
+        self.fire_rate *= rules.stat_boost;
+        
+        self.kills += other.kills;
+        self.damage_dealt += other.damage_dealt;
+        self.gain_xp(other.xp);
+    }
+
     pub fn get_sell_value(&self) -> u32 {
         self.cost / 2 + (self.level - 1) * (self.cost / 4)
     }
@@ -109,12 +621,134 @@ impl Tower {
         (self.x, self.y)
     }
 
+    pub fn contains_point(&self, x: f32, y: f32) -> bool {
+        let dx = x - self.x;
+        let dy = y - self.y;
+        dx * dx + dy * dy <= 12.0 * 12.0
+    }
+
+    // Returns true if the tower was destroyed
+    pub fn take_damage(&mut self, damage: u32) -> bool {
+        self.health = self.health.saturating_sub(damage);
+        self.health == 0
+    }
+
+    // Half a gold per missing point of health, rounded up
+    pub fn get_repair_cost(&self) -> u32 {
+        (self.max_health - self.health).div_ceil(2)
+    }
+
+    pub fn repair(&mut self) {
+        self.health = self.max_health;
+    }
+
+    pub fn get_health(&self) -> u32 {
+        self.health
+    }
+
+    pub fn get_max_health(&self) -> u32 {
+        self.max_health
+    }
+
+    pub fn get_level(&self) -> u32 {
+        self.level
+    }
+
     pub fn get_range(&self) -> f32 {
         self.range
     }
 
+    pub fn get_min_range(&self) -> f32 {
+        self.min_range
+    }
+
+    // Radius drawn around the tower: generators and detectors show how far
+    // they reach instead of a range
+    pub fn get_reach(&self) -> f32 {
+        if self.is_generator() {
+            self.grid_radius
+        } else if self.is_detector() {
+            self.get_detection_radius()
+        } else {
+            self.range
+        }
+    }
+
     pub fn get_damage(&self) -> u32 {
-        self.damage
+        (self.damage as f32 * (1.0 + self.rank as f32 * RANK_DAMAGE_BONUS)) as u32
+    }
+
+    // Damage per second the tower can put out right now; nothing while stunned
+    pub fn get_dps(&self) -> f32 {
+        if self.is_stunned() {
+            return 0.0;
+        }
+        self.get_damage() as f32 * self.get_fire_rate()
+    }
+
+    pub fn get_fire_rate(&self) -> f32 {
+        let overcharge = if self.is_ability_active(Ability::Overcharge) { 2.0 } else { 1.0 };
+        self.fire_rate * (1.0 + self.rank as f32 * RANK_FIRE_RATE_BONUS) * overcharge * self.power_ratio
+    }
+
+    pub fn is_generator(&self) -> bool {
+        self.power_output > 0.0
+    }
+
+    // Power this tower wants from the grid right now
+    pub fn get_power_demand(&self) -> f32 {
+        if self.powered_on { self.power_draw } else { 0.0 }
+    }
+
+    pub fn get_power_output(&self) -> f32 {
+        if self.powered_on { self.power_output } else { 0.0 }
+    }
+
+    pub fn get_grid_radius(&self) -> f32 {
+        self.grid_radius
+    }
+
+    pub fn is_detector(&self) -> bool {
+        self.tower_type == TowerType::Detector
+    }
+
+    // Radius in which stealth enemies are revealed, zero without power
+    pub fn get_detection_radius(&self) -> f32 {
+        if self.power_ratio <= 0.0 {
+            0.0
+        } else if !self.is_detector() && self.level >= DETECTION_LEVEL {
+            self.range
+        } else {
+            self.detection_radius
+        }
+    }
+
+    pub fn is_powered_on(&self) -> bool {
+        self.powered_on
+    }
+
+    pub fn toggle_power(&mut self) {
+        self.powered_on = !self.powered_on;
+    }
+
+    pub fn get_power_ratio(&self) -> f32 {
+        self.power_ratio
+    }
+
+    pub fn set_power_ratio(&mut self, ratio: f32) {
+        self.power_ratio = if self.powered_on { ratio.clamp(0.0, 1.0) } else { 0.0 };
+    }
+
+    pub fn get_kills(&self) -> u32 {
+        self.kills
+    }
+
+    pub fn get_damage_dealt(&self) -> u32 {
+        self.damage_dealt
+    }
+
+    pub fn get_rank(&self) -> u32 {
+        self.rank
     }
 
     pub fn get_tower_type(&self) -> TowerType {
@@ -122,11 +756,69 @@ impl Tower {
     }
 }
 
+// Signed shortest difference between two angles, in [-PI, PI]
+fn angle_diff(a: f32, b: f32) -> f32 {
+    (a - b + PI).rem_euclid(TAU) - PI
+}
+
 impl TowerType {
     pub fn cost(&self) -> u32 {
         match self {
             TowerType::Basic => 50,
             TowerType::Advanced => 100,
+            TowerType::Generator => 75,
+            TowerType::Mortar => 120,
+            TowerType::Detector => 60,
+            TowerType::Gravity => 90,
+            TowerType::Flak => 70,
+        }
+    }
+
+    pub fn merge_rules(&self) -> Option<MergeRules> {
+        match self {
+            TowerType::Basic => Some(MergeRules { max_level: 5, stat_boost: 1.15 }),
+            TowerType::Advanced => Some(MergeRules { max_level: 4, stat_boost: 1.25 }),
+            TowerType::Generator => None,
+            TowerType::Mortar => Some(MergeRules { max_level: 3, stat_boost: 1.2 }),
+            TowerType::Detector => None,
+            TowerType::Gravity => Some(MergeRules { max_level: 3, stat_boost: 1.15 }),
+            TowerType::Flak => Some(MergeRules { max_level: 4, stat_boost: 1.2 }),
+        }
+    }
+
+    pub fn target_layers(&self) -> TargetLayers {
+        match self {
+            TowerType::Basic => TargetLayers::Both,
+            TowerType::Advanced => TargetLayers::Ground,
+            TowerType::Generator => TargetLayers::Ground,
+            TowerType::Mortar => TargetLayers::Ground, // shells land on the ground
+            TowerType::Detector => TargetLayers::Ground,
+            TowerType::Gravity => TargetLayers::Ground, // can't drag flyers along the path
+            TowerType::Flak => TargetLayers::Air, // bursts are fused for altitude
+        }
+    }
+
+    pub fn damage_type(&self) -> DamageType {
+        match self {
+            TowerType::Basic => DamageType::Kinetic,
+            TowerType::Advanced => DamageType::Energy,
+            TowerType::Generator => DamageType::Kinetic,
+            TowerType::Mortar => DamageType::Concussive,
+            TowerType::Detector => DamageType::Kinetic,
+            TowerType::Gravity => DamageType::Kinetic,
+            TowerType::Flak => DamageType::Kinetic,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TowerType::Basic => "Basic",
+            TowerType::Advanced => "Advanced",
+            TowerType::Generator => "Generator",
+            TowerType::Mortar => "Mortar",
+            TowerType::Detector => "Detector",
+            TowerType::Gravity => "Gravity",
+            TowerType::Flak => "Flak",
         }
     }
 }
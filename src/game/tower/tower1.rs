@@ -11,8 +11,31 @@ impl Tower1 {
             range: 1000.0,
             damage: 10,
             fire_rate: 1.0, // 1 shot per second
+            min_range: 0.0,
             fire_cooldown: 0.0,
+            facing: 0.0,
+            base_facing: 0.0,
+            turn_rate: 6.0, // radians per second
+            firing_arc: None,
             level: 1,
+            health: 100,
+            max_health: 100,
+            kills: 0,
+            damage_dealt: 0,
+            xp: 0,
+            rank: 0,
+            ability_cooldown: 0.0,
+            ability_time: 0.0,
+            stun_time: 0.0,
+            power_draw: 2.0,
+            power_output: 0.0,
+            grid_radius: 0.0,
+            detection_radius: 0.0,
+            powered_on: true,
+            power_ratio: 1.0,
+            artillery: None,
+            force_target: None,
+            path_effect: None,
             cost: 50,
         }
     }
@@ -11,8 +11,31 @@ impl Tower2 {
             range: 1500.0,
             damage: 25,
             fire_rate: 0.5, // 0.5 shots per second (slower but more powerful)
+            min_range: 0.0,
             fire_cooldown: 0.0,
+            facing: 0.0,
+            base_facing: 0.0,
+            turn_rate: 1.5, // heavy turret turns slowly
+            firing_arc: Some(std::f32::consts::FRAC_PI_3), // 120 degree arc
             level: 1,
+            health: 150,
+            max_health: 150,
+            kills: 0,
+            damage_dealt: 0,
+            xp: 0,
+            rank: 0,
+            ability_cooldown: 0.0,
+            ability_time: 0.0,
+            stun_time: 0.0,
+            power_draw: 4.0,
+            power_output: 0.0,
+            grid_radius: 0.0,
+            detection_radius: 0.0,
+            powered_on: true,
+            power_ratio: 1.0,
+            artillery: None,
+            force_target: None,
+            path_effect: None,
             cost: 100,
         }
     }
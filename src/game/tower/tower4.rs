@@ -0,0 +1,45 @@
+use super::{Artillery, PathEffect, Tower, TowerType};
+
+pub struct Tower4;
+
+impl Tower4 {
+    pub fn create(x: f32, y: f32) -> Tower {
+        Tower {
+            tower_type: TowerType::Mortar,
+            x,
+            y,
+            range: 400.0,
+            min_range: 120.0, // Can't lob shells at enemies right next to it
+            damage: 40,
+            fire_rate: 0.33, // One shell every three seconds
+            fire_cooldown: 0.0,
+            facing: 0.0,
+            base_facing: 0.0,
+            turn_rate: 3.0,
+            firing_arc: None,
+            level: 1,
+            health: 180,
+            max_health: 180,
+            kills: 0,
+            damage_dealt: 0,
+            xp: 0,
+            rank: 0,
+            ability_cooldown: 0.0,
+            ability_time: 0.0,
+            stun_time: 0.0,
+            power_draw: 3.0,
+            power_output: 0.0,
+            grid_radius: 0.0,
+            detection_radius: 0.0,
+            powered_on: true,
+            power_ratio: 1.0,
+            artillery: Some(Artillery {
+                splash_radius: 40.0,
+                shell_speed: 250.0,
+            }),
+            force_target: None,
+            path_effect: Some(PathEffect::Knockback(25.0)), // The blast shoves enemies back down the path
+            cost: 120,
+        }
+    }
+}
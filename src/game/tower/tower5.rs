@@ -0,0 +1,42 @@
+use super::{Tower, TowerType};
+
+pub struct Tower5;
+
+impl Tower5 {
+    pub fn create(x: f32, y: f32) -> Tower {
+        Tower {
+            tower_type: TowerType::Detector,
+            x,
+            y,
+            range: 0.0, // Detectors don't shoot
+            damage: 0,
+            fire_rate: 0.0,
+            min_range: 0.0,
+            fire_cooldown: 0.0,
+            facing: 0.0,
+            base_facing: 0.0,
+            turn_rate: 0.0,
+            firing_arc: None,
+            level: 1,
+            health: 80,
+            max_health: 80,
+            kills: 0,
+            damage_dealt: 0,
+            xp: 0,
+            rank: 0,
+            ability_cooldown: 0.0,
+            ability_time: 0.0,
+            stun_time: 0.0,
+            power_draw: 1.0,
+            power_output: 0.0,
+            grid_radius: 0.0,
+            detection_radius: 160.0, // Reveals stealth enemies for every tower
+            powered_on: true,
+            power_ratio: 1.0,
+            artillery: None,
+            force_target: None,
+            path_effect: None,
+            cost: 60,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use super::{PathEffect, Tower, TowerType};
+
+pub struct Tower6;
+
+impl Tower6 {
+    pub fn create(x: f32, y: f32) -> Tower {
+        Tower {
+            tower_type: TowerType::Gravity,
+            x,
+            y,
+            range: 130.0,
+            damage: 4,
+            fire_rate: 1.5,
+            min_range: 0.0,
+            fire_cooldown: 0.0,
+            facing: 0.0,
+            base_facing: 0.0,
+            turn_rate: 6.0,
+            firing_arc: None,
+            level: 1,
+            health: 110,
+            max_health: 110,
+            kills: 0,
+            damage_dealt: 0,
+            xp: 0,
+            rank: 0,
+            ability_cooldown: 0.0,
+            ability_time: 0.0,
+            stun_time: 0.0,
+            power_draw: 3.0,
+            power_output: 0.0,
+            grid_radius: 0.0,
+            detection_radius: 0.0,
+            powered_on: true,
+            power_ratio: 1.0,
+            artillery: None,
+            force_target: None,
+            path_effect: Some(PathEffect::Pull { toward: (x, y), distance: 15.0 }), // Holds enemies near the tower
+            cost: 90,
+        }
+    }
+}
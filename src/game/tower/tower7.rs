@@ -0,0 +1,42 @@
+use super::{Tower, TowerType};
+
+pub struct Tower7;
+
+impl Tower7 {
+    pub fn create(x: f32, y: f32) -> Tower {
+        Tower {
+            tower_type: TowerType::Flak,
+            x,
+            y,
+            range: 220.0,
+            damage: 9,
+            fire_rate: 2.5, // Rapid bursts to shred flyers
+            min_range: 0.0,
+            fire_cooldown: 0.0,
+            facing: 0.0,
+            base_facing: 0.0,
+            turn_rate: 9.0,
+            firing_arc: None,
+            level: 1,
+            health: 90,
+            max_health: 90,
+            kills: 0,
+            damage_dealt: 0,
+            xp: 0,
+            rank: 0,
+            ability_cooldown: 0.0,
+            ability_time: 0.0,
+            stun_time: 0.0,
+            power_draw: 2.0,
+            power_output: 0.0,
+            grid_radius: 0.0,
+            detection_radius: 0.0,
+            powered_on: true,
+            power_ratio: 1.0,
+            artillery: None,
+            force_target: None,
+            path_effect: None,
+            cost: 70,
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use std::fmt;
+
+use crate::game::enemy::{Affix, EnemyType, FormationKind};
+
+// What a group sends down the path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaveUnit {
+    Enemy(EnemyType),
+    Formation(FormationKind),
+}
+
+// A batch of identical units within a wave
+#[derive(Debug, Clone)]
+pub struct WaveGroup {
+    pub unit: WaveUnit,
+    pub count: u32,
+    pub interval: f32,    // seconds between each unit of the group
+    pub delay: f32,       // seconds after the wave starts before the first unit
+    pub spawn_point: f32, // pixels along the route where the group appears
+    pub affixes: Vec<Affix>,
+}
+
+impl WaveGroup {
+    // Every enemy the group spawns, formations expanded into their members
+    pub fn enemy_types(&self) -> Vec<EnemyType> {
+        let unit: Vec<EnemyType> = match self.unit {
+            WaveUnit::Enemy(enemy_type) => vec![enemy_type],
+            WaveUnit::Formation(kind) => std::iter::once(kind.leader())
+                .chain(kind.slots().into_iter().map(|slot| slot.enemy_type))
+                .collect(),
+        };
+        (0..self.count).flat_map(|_| unit.iter().copied()).collect()
+    }
+}
+
+// A parse failure and the line of the wave file it happened on
+#[derive(Debug, Clone)]
+pub struct WaveScriptError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for WaveScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for WaveScriptError {}
+
+// The waves a map sends, read from its wave file. The format is one
+// `wave <n>` header per wave, numbered from 1, each followed by its groups:
+//
+//     wave 3
+//     normal count=6 interval=0.5 delay=2
+//     fast count=3 delay=6 spawn=400 mods=hasted,armored
+//
+// A group line starts with an enemy or formation id followed by any of
+// count (1), interval (0.5), delay (0), spawn (0) and mods (none), where
+// the defaults are in brackets. Everything after a `#` is a comment.
+pub struct WaveScript {
+    waves: Vec<Vec<WaveGroup>>,
+}
+
+impl WaveScript {
+    pub fn parse(source: &str) -> Result<Self, WaveScriptError> {
+        let mut waves: Vec<Vec<WaveGroup>> = Vec::new();
+        let mut header_line = 0;
+
+        for (index, raw) in source.lines().enumerate() {
+            let line = index + 1;
+            let text = raw.split('#').next().unwrap_or("").trim();
+            if text.is_empty() {
+                continue;
+            }
+            let error = |message: String| WaveScriptError { line, message };
+
+            let mut tokens = text.split_whitespace();
+            let first = tokens.next().unwrap_or("");
+
+            if first.eq_ignore_ascii_case("wave") {
+                if waves.last().is_some_and(|groups| groups.is_empty()) {
+                    return Err(WaveScriptError {
+                        line: header_line,
+                        message: format!("wave {} has no groups", waves.len()),
+                    });
+                }
+
+                let expected = waves.len() + 1;
+                let number = tokens.next()
+                    .ok_or_else(|| error("expected a wave number after 'wave'".to_string()))?;
+                match number.parse::<usize>() {
+                    Ok(number) if number == expected => {}
+                    Ok(number) => return Err(error(format!("expected wave {}, found wave {}", expected, number))),
+                    Err(_) => return Err(error(format!("'{}' is not a wave number", number))),
+                }
+                if let Some(extra) = tokens.next() {
+                    return Err(error(format!("unexpected '{}' after the wave number", extra)));
+                }
+
+                waves.push(Vec::new());
+                header_line = line;
+                continue;
+            }
+
+            let Some(groups) = waves.last_mut() else {
+                return Err(error("group before the first 'wave' header".to_string()));
+            };
+            groups.push(Self::parse_group(first, tokens).map_err(error)?);
+        }
+
+        match waves.last() {
+            None => Err(WaveScriptError {
+                line: source.lines().count(),
+                message: "no waves defined".to_string(),
+            }),
+            Some(groups) if groups.is_empty() => Err(WaveScriptError {
+                line: header_line,
+                message: format!("wave {} has no groups", waves.len()),
+            }),
+            Some(_) => Ok(Self { waves }),
+        }
+    }
+
+    fn parse_group<'a>(unit: &str, settings: impl Iterator<Item = &'a str>) -> Result<WaveGroup, String> {
+        let unit = Self::parse_unit(unit).ok_or_else(|| format!("unknown enemy or formation '{}'", unit))?;
+
+        let mut group = WaveGroup {
+            unit,
+            count: 1,
+            interval: 0.5,
+            delay: 0.0,
+            spawn_point: 0.0,
+            affixes: Vec::new(),
+        };
+        let mut seen: Vec<&str> = Vec::new();
+
+        for setting in settings {
+            let Some((key, value)) = setting.split_once('=') else {
+                return Err(format!("expected key=value, found '{}'", setting));
+            };
+            if seen.contains(&key) {
+                return Err(format!("'{}' is set more than once", key));
+            }
+            seen.push(key);
+
+            match key {
+                "count" => {
+                    group.count = value.parse().ok()
+                        .filter(|&count| count > 0)
+                        .ok_or_else(|| format!("count must be a whole number above 0, found '{}'", value))?;
+                }
+                "interval" => group.interval = Self::parse_amount(key, value)?,
+                "delay" => group.delay = Self::parse_amount(key, value)?,
+                "spawn" => group.spawn_point = Self::parse_amount(key, value)?,
+                "mods" => {
+                    for id in value.split(',') {
+                        let affix = Self::parse_affix(id).ok_or_else(|| format!("unknown modifier '{}'", id))?;
+                        if !group.affixes.contains(&affix) {
+                            group.affixes.push(affix);
+                        }
+                    }
+                }
+                _ => return Err(format!("unknown setting '{}'", key)),
+            }
+        }
+
+        if !group.affixes.is_empty() && matches!(group.unit, WaveUnit::Formation(_)) {
+            return Err("formations can't carry modifiers".to_string());
+        }
+        Ok(group)
+    }
+
+    // Ids used in wave files. They're kept apart from the names shown in the
+    // UI so that renaming a unit for players doesn't break any wave file.
+    fn parse_unit(id: &str) -> Option<WaveUnit> {
+        let unit = match id {
+            "normal" => WaveUnit::Enemy(EnemyType::Normal),
+            "fast" => WaveUnit::Enemy(EnemyType::Fast),
+            "boss" => WaveUnit::Enemy(EnemyType::Boss),
+            "flying" => WaveUnit::Enemy(EnemyType::Flying),
+            "splitter" => WaveUnit::Enemy(EnemyType::Splitter),
+            "splitling" => WaveUnit::Enemy(EnemyType::Splitling),
+            "healer" => WaveUnit::Enemy(EnemyType::Healer),
+            "warden" => WaveUnit::Enemy(EnemyType::Warden),
+            "shielded" => WaveUnit::Enemy(EnemyType::Shielded),
+            "stealth" => WaveUnit::Enemy(EnemyType::Stealth),
+            "sapper" => WaveUnit::Enemy(EnemyType::Sapper),
+            "disruptor" => WaveUnit::Enemy(EnemyType::Disruptor),
+            "blinker" => WaveUnit::Enemy(EnemyType::Blinker),
+            "burrower" => WaveUnit::Enemy(EnemyType::Burrower),
+            "swarmer" => WaveUnit::Enemy(EnemyType::Swarmer),
+            "escort" => WaveUnit::Formation(FormationKind::Escort),
+            "phalanx" => WaveUnit::Formation(FormationKind::Phalanx),
+            _ => return None,
+        };
+        Some(unit)
+    }
+
+    fn parse_affix(id: &str) -> Option<Affix> {
+        match id {
+            "armored" => Some(Affix::Armored),
+            "hasted" => Some(Affix::Hasted),
+            "regenerating" => Some(Affix::Regenerating),
+            "vampiric" => Some(Affix::Vampiric),
+            "explosive" => Some(Affix::Explosive),
+            _ => None,
+        }
+    }
+
+    // Any non-negative number: a time in seconds or a distance in pixels
+    fn parse_amount(key: &str, value: &str) -> Result<f32, String> {
+        value.parse::<f32>().ok()
+            .filter(|number| number.is_finite() && *number >= 0.0)
+            .ok_or_else(|| format!("{} must be a number of at least 0, found '{}'", key, value))
+    }
+
+    // Groups of a wave, numbered from 1; empty past the end of the script
+    pub fn get_wave(&self, number: u32) -> &[WaveGroup] {
+        (number as usize)
+            .checked_sub(1)
+            .and_then(|index| self.waves.get(index))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn wave_count(&self) -> u32 {
+        self.waves.len() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(source: &str) -> WaveScriptError {
+        match WaveScript::parse(source) {
+            Ok(_) => panic!("expected a parse error"),
+            Err(err) => err,
+        }
+    }
+
+    #[test]
+    fn shipped_wave_files_parse() {
+        for (name, source) in [
+            ("beginner", include_str!("../map/waves/beginner.waves")),
+            ("advanced", include_str!("../map/waves/advanced.waves")),
+        ] {
+            let script = WaveScript::parse(source)
+                .unwrap_or_else(|err| panic!("{}.waves, {}", name, err));
+            assert_eq!(script.wave_count(), 20, "{}.waves", name);
+        }
+    }
+
+    #[test]
+    fn parses_groups_with_defaults_and_settings() {
+        let script = WaveScript::parse(
+            "# comment\n\
+             wave 1\n\
+             normal\n\
+             fast count=3 interval=0.4 delay=5 spawn=300 mods=hasted,armored # trailing\n\
+             wave 2\n\
+             escort count=2\n",
+        ).unwrap();
+
+        let first = script.get_wave(1);
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].unit, WaveUnit::Enemy(EnemyType::Normal));
+        assert_eq!(first[0].count, 1);
+        assert_eq!(first[1].unit, WaveUnit::Enemy(EnemyType::Fast));
+        assert_eq!(first[1].count, 3);
+        assert_eq!(first[1].spawn_point, 300.0);
+        assert_eq!(first[1].affixes, vec![Affix::Hasted, Affix::Armored]);
+        assert_eq!(script.get_wave(2)[0].enemy_types().len(), 10);
+        assert!(script.get_wave(3).is_empty());
+    }
+
+    #[test]
+    fn error_points_at_the_bad_line() {
+        let err = parse_error("wave 1\nnormal count=2\n\nnormal count=zero\n");
+        assert_eq!(err.line, 4);
+        assert_eq!(err.to_string(), "line 4: count must be a whole number above 0, found 'zero'");
+    }
+
+    #[test]
+    fn rejects_unknown_names() {
+        let err = parse_error("wave 1\ngrunt count=2\n");
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("unknown enemy or formation 'grunt'"));
+
+        let err = parse_error("wave 1\nnormal mods=shiny\n");
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("unknown modifier 'shiny'"));
+
+        let err = parse_error("wave 1\nnormal speed=2\n");
+        assert!(err.message.contains("unknown setting 'speed'"));
+    }
+
+    #[test]
+    fn rejects_duplicate_keys() {
+        let err = parse_error("wave 1\nnormal count=2 delay=1 count=3\n");
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("'count' is set more than once"));
+    }
+
+    #[test]
+    fn rejects_out_of_order_waves() {
+        let err = parse_error("wave 1\nnormal\nwave 3\nnormal\n");
+        assert_eq!(err.line, 3);
+        assert!(err.message.contains("expected wave 2, found wave 3"));
+    }
+
+    #[test]
+    fn rejects_empty_waves_and_groups_outside_a_wave() {
+        let err = parse_error("wave 1\n\nwave 2\nnormal\n");
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("wave 1 has no groups"));
+
+        let err = parse_error("normal\nwave 1\n");
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("before the first 'wave' header"));
+    }
+}
@@ -1,14 +1,37 @@
 use ggez::{Context, GameResult, graphics};
 use ggez::graphics::{DrawMode, DrawParam, Mesh, Rect, Text};
 use ggez::event::MouseButton;
+use ggez::input::keyboard::{KeyCode, KeyMods};
 use glam::Vec2;
+use hecs::Entity;
 
 use crate::game::Game;
-use crate::game::tower::TowerType;
-use crate::game::GameState;
+use crate::game::tower::{Tower, TowerType};
+use crate::game::powerup::PowerupType;
+use crate::game::{BossStatus, GameState, WavePreview};
+
+// Towers that can be bought from the button bar, in button order. The bar
+// ends with the Upgrade, Sell and Cancel buttons.
+const BUILD_OPTIONS: [TowerType; 7] = [
+    TowerType::Basic,
+    TowerType::Advanced,
+    TowerType::Generator,
+    TowerType::Mortar,
+    TowerType::Detector,
+    TowerType::Gravity,
+    TowerType::Flak,
+];
+const BUTTON_COUNT: usize = BUILD_OPTIONS.len() + 3;
+const BUTTON_WIDTH: f32 = 1024.0 / BUTTON_COUNT as f32;
+
+// Selected-tower panel in the top right corner
+const PANEL_RECT: Rect = Rect::new(784.0, 10.0, 230.0, 130.0);
+const ABILITY_BUTTON_RECT: Rect = Rect::new(794.0, 100.0, 210.0, 30.0);
 
 pub struct Gui {
     selected_tower: Option<TowerType>,
+    selected_entity: Option<Entity>, // placed tower the upgrade/sell buttons act on
+    dragging: Option<Entity>,        // tower being dragged onto another to merge
     ui_state: UiState,
 }
 
@@ -23,6 +46,8 @@ impl Gui {
     pub fn new(_ctx: &mut Context) -> GameResult<Self> {
         Ok(Self {
             selected_tower: None,
+            selected_entity: None,
+            dragging: None,
             ui_state: UiState::Main,
         })
     }
@@ -34,33 +59,101 @@ impl Gui {
             _ => {}
         }
         
+        // Call the next wave early for a gold bonus
+        if ctx.keyboard.is_key_just_pressed(KeyCode::N) {
+            game.call_next_wave();
+        }
+        
+        // Undo/redo build actions made since the last wave started
+        if ctx.keyboard.is_mod_active(KeyMods::CTRL) {
+            if ctx.keyboard.is_key_just_pressed(KeyCode::Z) {
+                game.undo();
+            } else if ctx.keyboard.is_key_just_pressed(KeyCode::Y) {
+                game.redo();
+            }
+        }
+        
+        if ctx.keyboard.is_key_just_pressed(KeyCode::M) {
+            game.toggle_merge_mode();
+        }
+        
+        if ctx.keyboard.is_key_just_pressed(KeyCode::O) {
+            game.toggle_overkill_prevention();
+        }
+        
+        if ctx.keyboard.is_key_just_pressed(KeyCode::G) {
+            game.toggle_power_grid();
+        }
+        
+        if ctx.keyboard.is_key_just_pressed(KeyCode::D) {
+            game.toggle_duplicate_pricing();
+        }
+        
+        // Show what each route costs enemies
+        if ctx.keyboard.is_key_just_pressed(KeyCode::B) {
+            game.toggle_route_debug();
+        }
+        
+        // Switch the selected tower off to free up grid power, or back on
+        if ctx.keyboard.is_key_just_pressed(KeyCode::P) && let Some(entity) = self.selected_entity {
+            game.toggle_tower_power(entity);
+        }
+        
+        // Trigger the selected tower's active ability
+        if ctx.keyboard.is_key_just_pressed(KeyCode::Q) && let Some(entity) = self.selected_entity {
+            game.activate_tower_ability(entity);
+        }
+        
+        // Repair the selected tower
+        if ctx.keyboard.is_key_just_pressed(KeyCode::H) && let Some(entity) = self.selected_entity {
+            game.repair_tower(entity);
+        }
+        
+        // Buy a reveal powerup to uncover every stealth enemy for a while,
+        // unless one is already running
+        if ctx.keyboard.is_key_just_pressed(KeyCode::R) && !game.is_powerup_active(PowerupType::Reveal) {
+            game.activate_powerup(PowerupType::Reveal);
+        }
+        
+        // Undo may have removed the selected tower
+        if let Some(entity) = self.selected_entity && game.get_tower(entity).is_none() {
+            self.selected_entity = None;
+        }
+        
         // Update to use context properties instead of deprecated functions
-        if ctx.mouse.button_pressed(MouseButton::Left) {
+        if ctx.mouse.button_just_pressed(MouseButton::Left) {
             let mouse_pos = ctx.mouse.position();
             
             // Check UI button clicks
             if mouse_pos.y >= 700.0 {  // Adjusted for larger window
-                if mouse_pos.x < 200.0 {
-                    // Basic tower button
-                    self.selected_tower = Some(TowerType::Basic);
-                    self.ui_state = UiState::TowerPlacement;
-                } else if mouse_pos.x < 400.0 {
-                    // Advanced tower button
-                    self.selected_tower = Some(TowerType::Advanced);
+                let button = (mouse_pos.x / BUTTON_WIDTH) as usize;
+                if button < BUILD_OPTIONS.len() {
+                    // Tower buttons
+                    self.selected_tower = Some(BUILD_OPTIONS[button]);
                     self.ui_state = UiState::TowerPlacement;
-                } else if mouse_pos.x < 600.0 {
-                    // Upgrade tower button (implementation would go here)
-                    // For now just cancel selection
+                } else if button == BUILD_OPTIONS.len() {
+                    // Upgrade the selected tower
+                    if let Some(entity) = self.selected_entity {
+                        game.upgrade_tower(entity);
+                    }
                     self.selected_tower = None;
                     self.ui_state = UiState::Main;
-                } else if mouse_pos.x < 800.0 {
-                    // Sell tower button (implementation would go here)
+                } else if button == BUILD_OPTIONS.len() + 1 {
+                    // Sell the selected tower
+                    if let Some(entity) = self.selected_entity.take() {
+                        game.sell_tower(entity);
+                    }
                     self.selected_tower = None;
                     self.ui_state = UiState::Main;
                 } else {
                     // Cancel/Pause button
                     game.toggle_pause();
                 }
+            } else if self.selected_entity.is_some() && PANEL_RECT.contains(mouse_pos) {
+                // Clicks inside the selected-tower panel keep the selection
+                if let Some(entity) = self.selected_entity && ABILITY_BUTTON_RECT.contains(mouse_pos) {
+                    game.activate_tower_ability(entity);
+                }
             } else if let UiState::TowerPlacement = self.ui_state {
                 // Place tower
                 if let Some(tower_type) = self.selected_tower {
@@ -69,6 +162,32 @@ impl Gui {
                         self.ui_state = UiState::Main;
                     }
                 }
+            } else if let UiState::Main = self.ui_state {
+                // Select a placed tower, and start dragging it in merge mode
+                self.selected_entity = game.tower_at(mouse_pos.x, mouse_pos.y);
+                if game.is_merge_enabled() {
+                    self.dragging = self.selected_entity;
+                }
+            }
+        }
+        
+        // Right click fixes the ground point an artillery tower shells;
+        // clicking outside its firing ring goes back to automatic targeting
+        if ctx.mouse.button_just_pressed(MouseButton::Right)
+            && let Some(entity) = self.selected_entity
+            && game.get_tower(entity).is_some_and(|tower| tower.has_ground_targeting())
+        {
+            let mouse_pos = ctx.mouse.position();
+            if !game.set_tower_force_target(entity, Some((mouse_pos.x, mouse_pos.y))) {
+                game.set_tower_force_target(entity, None);
+            }
+        }
+        
+        // Drop a dragged tower onto another one to merge them
+        if ctx.mouse.button_just_released(MouseButton::Left) && let Some(source) = self.dragging.take() {
+            let mouse_pos = ctx.mouse.position();
+            if let Some(target) = game.tower_at(mouse_pos.x, mouse_pos.y) && game.merge_towers(source, target) {
+                self.selected_entity = Some(target);
             }
         }
         
@@ -103,30 +222,212 @@ impl Gui {
                 .color([1.0f32, 1.0f32, 1.0f32, 1.0f32]),
         );
         
+        if let Some(boss) = game.get_boss_status() {
+            self.draw_boss_bar(ctx, &boss, canvas)?;
+        }
+        
+        self.draw_wave_preview(&game.get_wave_preview(), game.is_powerup_active(PowerupType::Reveal), canvas);
+        
+        // Draw economy projections for the coming wave
+        let economy_text = Text::new(format!(
+            "Next wave in {:.0}s | Interest: +{}g | Call now [N]: +{}g | {} | Rising prices [D]: {}",
+            game.get_wave_cooldown().ceil(),
+            game.get_projected_interest(),
+            game.get_early_call_bonus(),
+            game.get_difficulty().as_str(),
+            if game.is_duplicate_pricing_enabled() { "on" } else { "off" }
+        ));
+        
+        canvas.draw(
+            &economy_text,
+            DrawParam::default()
+                .dest(Vec2::new(10.0, 30.0))
+                .color([1.0f32, 0.9f32, 0.4f32, 1.0f32]),
+        );
+        
+        // Draw undo/redo availability for the current build phase
+        if game.get_undo_count() > 0 || game.get_redo_count() > 0 {
+            let history_text = Text::new(format!(
+                "Undo [Ctrl+Z]: {} | Redo [Ctrl+Y]: {}",
+                game.get_undo_count(),
+                game.get_redo_count()
+            ));
+            
+            canvas.draw(
+                &history_text,
+                DrawParam::default()
+                    .dest(Vec2::new(10.0, 50.0))
+                    .color([0.8f32, 0.8f32, 0.8f32, 1.0f32]),
+            );
+        }
+        
+        // Damage efficiency, to compare with and without overkill prevention
+        let overkill_text = Text::new(format!(
+            "Overkill prevention [O]: {} | Wasted damage: {:.0}%",
+            if game.is_overkill_prevention_enabled() { "on" } else { "off" },
+            game.get_wasted_damage_ratio() * 100.0
+        ));
+        canvas.draw(
+            &overkill_text,
+            DrawParam::default()
+                .dest(Vec2::new(10.0, 110.0))
+                .color([0.8f32, 0.8f32, 1.0f32, 1.0f32]),
+        );
+        
+        if game.is_power_grid_enabled() {
+            let power_text = Text::new("Power grid [G]: towers need a generator nearby | [P] switch selected tower");
+            canvas.draw(
+                &power_text,
+                DrawParam::default()
+                    .dest(Vec2::new(10.0, 90.0))
+                    .color([1.0f32, 0.9f32, 0.3f32, 1.0f32]),
+            );
+        }
+        
+        if game.is_route_debug_enabled() {
+            let route_text = Text::new("Route costs [B]: length plus tower threat, enemies take the cheapest");
+            canvas.draw(
+                &route_text,
+                DrawParam::default()
+                    .dest(Vec2::new(10.0, 185.0))
+                    .color([0.4f32, 1.0f32, 0.4f32, 1.0f32]),
+            );
+        }
+        
+        if game.is_merge_enabled() {
+            let merge_text = Text::new("Merge mode [M]: drag a tower onto a matching one");
+            canvas.draw(
+                &merge_text,
+                DrawParam::default()
+                    .dest(Vec2::new(10.0, 70.0))
+                    .color([0.6f32, 1.0f32, 0.6f32, 1.0f32]),
+            );
+        }
+        
+        // Draw the tower being dragged for a merge once it leaves its slot
+        let mouse_pos = ctx.mouse.position();
+        let dragged = self.dragging
+            .and_then(|entity| game.get_tower(entity))
+            .filter(|tower| !tower.contains_point(mouse_pos.x, mouse_pos.y));
+        if let Some(tower) = dragged {
+            let (x, y) = tower.get_position();
+            let drag_line = Mesh::new_line(
+                ctx,
+                &[Vec2::new(x, y), Vec2::new(mouse_pos.x, mouse_pos.y)],
+                2.0,
+                [1.0, 1.0, 1.0, 0.5].into(),
+            )?;
+            canvas.draw(&drag_line, DrawParam::default());
+            
+            let ghost = Mesh::new_circle(
+                ctx,
+                DrawMode::fill(),
+                Vec2::new(mouse_pos.x, mouse_pos.y),
+                10.0,
+                0.1,
+                [1.0, 1.0, 1.0, 0.4].into(),
+            )?;
+            canvas.draw(&ghost, DrawParam::default());
+        }
+        
+        // Highlight the selected tower and its range
+        let selected = self.selected_entity.and_then(|entity| game.get_tower(entity));
+        if let Some(tower) = &selected {
+            let (x, y) = tower.get_position();
+            let selection_ring = Mesh::new_circle(
+                ctx,
+                DrawMode::stroke(2.0),
+                Vec2::new(x, y),
+                14.0,
+                0.1,
+                [1.0, 1.0, 0.0, 1.0].into(),
+            )?;
+            canvas.draw(&selection_ring, DrawParam::default());
+            
+            let range_circle = Mesh::new_circle(
+                ctx,
+                DrawMode::stroke(1.0),
+                Vec2::new(x, y),
+                tower.get_reach(),
+                0.5,
+                [1.0, 1.0, 1.0, 0.3].into(),
+            )?;
+            canvas.draw(&range_circle, DrawParam::default());
+            
+            // Artillery can't hit anything inside its minimum range
+            if tower.get_min_range() > 0.0 {
+                let dead_zone = Mesh::new_circle(
+                    ctx,
+                    DrawMode::stroke(1.0),
+                    Vec2::new(x, y),
+                    tower.get_min_range(),
+                    0.5,
+                    [1.0, 0.3, 0.3, 0.4].into(),
+                )?;
+                canvas.draw(&dead_zone, DrawParam::default());
+            }
+            
+            // Crosshair on the ground point the tower was told to shell
+            if let Some((fx, fy)) = tower.get_force_target() {
+                for (from, to) in [((fx - 8.0, fy), (fx + 8.0, fy)), ((fx, fy - 8.0), (fx, fy + 8.0))] {
+                    let crosshair = Mesh::new_line(
+                        ctx,
+                        &[Vec2::new(from.0, from.1), Vec2::new(to.0, to.1)],
+                        2.0,
+                        [1.0, 0.3, 0.1, 1.0].into(),
+                    )?;
+                    canvas.draw(&crosshair, DrawParam::default());
+                }
+            }
+            
+            self.draw_tower_panel(ctx, tower, canvas)?;
+        }
+        
+        let upgrade_label = match &selected {
+            Some(tower) => format!("Upgrade ({}g)", tower.get_upgrade_cost()),
+            None => "Upgrade".to_string(),
+        };
+        let sell_label = match &selected {
+            Some(tower) => format!("Sell (+{}g)", tower.get_sell_value()),
+            None => "Sell".to_string(),
+        };
+        drop(selected);
+        
         // Draw buttons - adjust for larger window
-        let buttons = [
-            ("Basic Tower (50g)", [0.5f32, 0.5f32, 1.0f32, 1.0f32]),
-            ("Advanced Tower (100g)", [0.8f32, 0.4f32, 0.8f32, 1.0f32]),
-            ("Upgrade Tower (75g)", [0.2f32, 0.8f32, 0.2f32, 1.0f32]),
-            ("Sell Tower", [0.8f32, 0.7f32, 0.2f32, 1.0f32]),
-            ("Cancel", [0.8f32, 0.2f32, 0.2f32, 1.0f32]),
-        ];
+        let mut buttons: Vec<(String, [f32; 4])> = BUILD_OPTIONS.iter()
+            .map(|tower_type| {
+                let label = format!("{} ({}g)", tower_type.name(), game.get_tower_price(*tower_type));
+                let color = match tower_type {
+                    TowerType::Basic => [0.5f32, 0.5f32, 1.0f32, 1.0f32],
+                    TowerType::Advanced => [0.8f32, 0.4f32, 0.8f32, 1.0f32],
+                    TowerType::Generator => [0.7f32, 0.6f32, 0.1f32, 1.0f32],
+                    TowerType::Mortar => [0.5f32, 0.5f32, 0.3f32, 1.0f32],
+                    TowerType::Detector => [0.3f32, 0.8f32, 0.8f32, 1.0f32],
+                    TowerType::Gravity => [0.5f32, 0.3f32, 0.7f32, 1.0f32],
+                    TowerType::Flak => [0.8f32, 0.4f32, 0.3f32, 1.0f32],
+                };
+                (label, color)
+            })
+            .collect();
+        buttons.push((upgrade_label, [0.2f32, 0.8f32, 0.2f32, 1.0f32]));
+        buttons.push((sell_label, [0.8f32, 0.7f32, 0.2f32, 1.0f32]));
+        buttons.push(("Cancel".to_string(), [0.8f32, 0.2f32, 0.2f32, 1.0f32]));
         
         for (i, (label, color)) in buttons.iter().enumerate() {
             let button_rect = Mesh::new_rectangle(
                 ctx,
                 DrawMode::fill(),
-                Rect::new(i as f32 * 200.0, 710.0, 190.0, 40.0),
+                Rect::new(i as f32 * BUTTON_WIDTH, 710.0, BUTTON_WIDTH - 10.0, 40.0),
                 (*color).into(),
             )?;
             
             canvas.draw(&button_rect, DrawParam::default());
             
-            let button_text = Text::new(*label);
+            let button_text = Text::new(label.as_str());
             canvas.draw(
                 &button_text,
                 DrawParam::default()
-                    .dest(Vec2::new(i as f32 * 200.0 + 10.0, 720.0))
+                    .dest(Vec2::new(i as f32 * BUTTON_WIDTH + 10.0, 720.0))
                     .color([1.0f32, 1.0f32, 1.0f32, 1.0f32]),
             );
         }
@@ -134,16 +435,14 @@ impl Gui {
         // Highlight selected tower type
         if let UiState::TowerPlacement = self.ui_state {
             // Draw a box around the selected tower type
-            let highlight_index = match self.selected_tower {
-                Some(TowerType::Basic) => 0,
-                Some(TowerType::Advanced) => 1,
-                None => 4, // Cancel button
-            };
+            let highlight_index = self.selected_tower
+                .and_then(|selected| BUILD_OPTIONS.iter().position(|tower_type| *tower_type == selected))
+                .unwrap_or(BUTTON_COUNT - 1); // Cancel button
             
             let highlight_rect = Mesh::new_rectangle(
                 ctx,
                 DrawMode::stroke(2.0),
-                Rect::new(highlight_index as f32 * 200.0, 710.0, 190.0, 40.0),
+                Rect::new(highlight_index as f32 * BUTTON_WIDTH, 710.0, BUTTON_WIDTH - 10.0, 40.0),
                 [1.0f32, 1.0f32, 0.0f32, 1.0f32].into(), // Added .into() to convert to Color
             )?;
             
@@ -173,26 +472,161 @@ impl Gui {
                 );
             },
             UiState::TowerPlacement => {
-                // Draw placement indicator
+                // Draw placement indicator, sized from the tower the button builds
                 let mouse_pos = ctx.mouse.position();
-                let tower_range_circle = Mesh::new_circle(
-                    ctx,
-                    DrawMode::stroke(2.0),
-                    Vec2::new(mouse_pos.x, mouse_pos.y),
-                    match self.selected_tower {
-                        Some(TowerType::Basic) => 100.0,
-                        Some(TowerType::Advanced) => 150.0,
-                        None => 0.0,
-                    },
-                    0.1,
-                    [1.0, 1.0, 1.0, 0.5].into(),
-                )?;
-                
-                canvas.draw(&tower_range_circle, DrawParam::default());
+                if let Some(tower_type) = self.selected_tower {
+                    let preview = Tower::new(tower_type, mouse_pos.x, mouse_pos.y);
+                    let tower_range_circle = Mesh::new_circle(
+                        ctx,
+                        DrawMode::stroke(2.0),
+                        Vec2::new(mouse_pos.x, mouse_pos.y),
+                        preview.get_reach(),
+                        0.1,
+                        [1.0, 1.0, 1.0, 0.5].into(),
+                    )?;
+                    
+                    canvas.draw(&tower_range_circle, DrawParam::default());
+                    
+                    // Ring-shaped preview for towers with a minimum range
+                    if preview.get_min_range() > 0.0 {
+                        let dead_zone_circle = Mesh::new_circle(
+                            ctx,
+                            DrawMode::stroke(2.0),
+                            Vec2::new(mouse_pos.x, mouse_pos.y),
+                            preview.get_min_range(),
+                            0.1,
+                            [1.0, 0.3, 0.3, 0.5].into(),
+                        )?;
+                        
+                        canvas.draw(&dead_zone_circle, DrawParam::default());
+                    }
+                }
             },
             _ => {}
         }
         
         Ok(())
     }
+
+    // List what the next wave brings and warn about stealth enemies
+    fn draw_wave_preview(&self, preview: &WavePreview, revealing: bool, canvas: &mut graphics::Canvas) {
+        let enemies: Vec<String> = preview.enemies.iter()
+            .map(|(enemy_type, count)| format!("{}x {}", count, enemy_type.name()))
+            .collect();
+        let mut text = format!("Wave {}: {}", preview.wave, enemies.join(", "));
+        if let Some(formation) = preview.formation {
+            text.push_str(&format!(" | {} formation", formation.name()));
+        }
+        if preview.stealth > 0 {
+            text.push_str(&format!(" | {} stealth - build a detector or reveal [R]", preview.stealth));
+        }
+        if revealing {
+            text.push_str(" | Reveal active");
+        }
+        
+        canvas.draw(
+            &Text::new(text),
+            DrawParam::default()
+                .dest(Vec2::new(10.0, 165.0))
+                .color(if preview.stealth > 0 {
+                    [1.0f32, 0.5f32, 0.3f32, 1.0f32]
+                } else {
+                    [0.8f32, 0.8f32, 0.8f32, 1.0f32]
+                }),
+        );
+    }
+
+    // Wide health bar across the top of the field while a boss is alive
+    fn draw_boss_bar(&self, ctx: &mut Context, boss: &BossStatus, canvas: &mut graphics::Canvas) -> GameResult {
+        let bar = Rect::new(262.0, 140.0, 500.0, 18.0);
+        let health_pct = boss.health as f32 / boss.max_health as f32;
+        
+        let background = Mesh::new_rectangle(ctx, DrawMode::fill(), bar, [0.2, 0.0, 0.0, 0.8].into())?;
+        canvas.draw(&background, DrawParam::default());
+        
+        let health = Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(bar.x, bar.y, bar.w * health_pct, bar.h),
+            [0.9, 0.2, 0.1, 1.0].into(),
+        )?;
+        canvas.draw(&health, DrawParam::default());
+        
+        let border = Mesh::new_rectangle(ctx, DrawMode::stroke(2.0), bar, [1.0, 0.8, 0.2, 1.0].into())?;
+        canvas.draw(&border, DrawParam::default());
+        
+        let label = if boss.shield > 0 {
+            format!("{} - {}/{} (shield {})", boss.name, boss.health, boss.max_health, boss.shield)
+        } else {
+            format!("{} - {}/{}", boss.name, boss.health, boss.max_health)
+        };
+        let boss_text = Text::new(label);
+        canvas.draw(
+            &boss_text,
+            DrawParam::default()
+                .dest(Vec2::new(bar.x + 8.0, bar.y + 1.0))
+                .color([1.0f32, 1.0f32, 1.0f32, 1.0f32]),
+        );
+        
+        Ok(())
+    }
+
+    fn draw_tower_panel(&self, ctx: &mut Context, tower: &Tower, canvas: &mut graphics::Canvas) -> GameResult {
+        let panel_bg = Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            PANEL_RECT,
+            [0.15, 0.15, 0.15, 0.9].into(),
+        )?;
+        canvas.draw(&panel_bg, DrawParam::default());
+        
+        let info_text = Text::new(format!(
+            "{} Tower - Level {} - Rank {}\nKills: {} | Damage: {} | Hits: {}\nDPS: {:.1} {} | Power: {}\nHealth: {}/{} | Repair [H]: {}g",
+            tower.get_tower_type().name(),
+            tower.get_level(),
+            tower.get_rank(),
+            tower.get_kills(),
+            tower.get_damage_dealt(),
+            tower.get_tower_type().target_layers().name(),
+            tower.get_damage() as f32 * tower.get_fire_rate(),
+            tower.get_tower_type().damage_type().name(),
+            if tower.is_powered_on() {
+                format!("{:.0}%", tower.get_power_ratio() * 100.0)
+            } else {
+                "off".to_string()
+            },
+            tower.get_health(),
+            tower.get_max_health(),
+            tower.get_repair_cost()
+        ));
+        canvas.draw(
+            &info_text,
+            DrawParam::default()
+                .dest(Vec2::new(PANEL_RECT.x + 10.0, PANEL_RECT.y + 10.0))
+                .color([1.0f32, 1.0f32, 1.0f32, 1.0f32]),
+        );
+        
+        // Ability button, greyed out while recharging
+        if let Some(ability) = tower.get_ability() {
+            let cooldown = tower.get_ability_cooldown();
+            let (label, color) = if cooldown > 0.0 {
+                (format!("{} ({:.0}s)", ability.name(), cooldown.ceil()), [0.35f32, 0.35f32, 0.35f32, 1.0f32])
+            } else {
+                (format!("[Q] {}", ability.name()), [0.2f32, 0.5f32, 0.9f32, 1.0f32])
+            };
+            
+            let button = Mesh::new_rectangle(ctx, DrawMode::fill(), ABILITY_BUTTON_RECT, color.into())?;
+            canvas.draw(&button, DrawParam::default());
+            
+            let button_text = Text::new(label);
+            canvas.draw(
+                &button_text,
+                DrawParam::default()
+                    .dest(Vec2::new(ABILITY_BUTTON_RECT.x + 8.0, ABILITY_BUTTON_RECT.y + 7.0))
+                    .color([1.0f32, 1.0f32, 1.0f32, 1.0f32]),
+            );
+        }
+        
+        Ok(())
+    }
 }
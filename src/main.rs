@@ -2,7 +2,9 @@ use ggez::{Context, ContextBuilder, GameResult};
 use ggez::event::{self, EventHandler};
 use ggez::graphics;
 use ggez::conf::{WindowMode, WindowSetup};
-use crate::game::Game;
+use ggez::input::keyboard::KeyCode;
+use crate::game::{Game, GameState};
+use crate::game::menu::{MenuAction, PauseMenu};
 use crate::gui::Gui;
 
 mod game;
@@ -22,9 +24,10 @@ fn main() -> GameResult {
     // Create game state
     let game = Game::new(&mut ctx)?;
     let gui = Gui::new(&mut ctx)?;
+    let menu = PauseMenu::new();
     
     // Create main game state
-    let state = MainState { game, gui };
+    let state = MainState { game, gui, menu };
     
     // Run the game
     event::run(ctx, event_loop, state)
@@ -33,10 +36,64 @@ fn main() -> GameResult {
 struct MainState {
     game: Game,
     gui: Gui,
+    menu: PauseMenu,
+}
+
+// Keys the pause menu listens to
+const MENU_KEYS: [KeyCode; 6] = [
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::Return,
+    KeyCode::Escape,
+];
+
+impl MainState {
+    // While paused the menu takes the keyboard; Escape opens it during play
+    fn update_menu(&mut self, ctx: &mut Context) -> GameResult {
+        match self.game.get_game_state() {
+            GameState::Playing => {
+                if ctx.keyboard.is_key_just_pressed(KeyCode::Escape) {
+                    self.game.toggle_pause();
+                }
+                return Ok(());
+            }
+            GameState::Paused => {}
+            _ => return Ok(()),
+        }
+        
+        for key in MENU_KEYS {
+            if !ctx.keyboard.is_key_just_pressed(key) {
+                continue;
+            }
+            
+            let action = self.menu.handle_key_press(key);
+            if self.menu.difficulty() != self.game.get_difficulty() {
+                self.game.set_difficulty(self.menu.difficulty());
+            }
+            
+            match action {
+                Some(MenuAction::Resume) => self.game.toggle_pause(),
+                // There's no title screen yet, so quitting starts a fresh game
+                Some(MenuAction::QuitToMainMenu) => {
+                    self.game = Game::new(ctx)?;
+                    self.game.set_difficulty(self.menu.difficulty());
+                    self.gui = Gui::new(ctx)?;
+                }
+                Some(MenuAction::ExitGame) => ctx.request_quit(),
+                None => continue,
+            }
+            break;
+        }
+        Ok(())
+    }
 }
 
 impl EventHandler for MainState {
     fn update(&mut self, ctx: &mut Context) -> GameResult {
+        self.update_menu(ctx)?;
+        
         // Update game logic
         self.game.update(ctx)?;
         
@@ -56,6 +113,10 @@ impl EventHandler for MainState {
         // Draw GUI overlay
         self.gui.draw(ctx, &self.game, &mut canvas)?;
         
+        if let GameState::Paused = self.game.get_game_state() {
+            self.menu.draw(ctx, &mut canvas)?;
+        }
+        
         // Finish drawing
         canvas.finish(ctx)?;
         Ok(())